@@ -0,0 +1,21 @@
+#![allow(missing_docs)]
+
+use std::{any::TypeId, time::Duration};
+
+use common::{contains_bench, extend_bench};
+use criterion::{Criterion, criterion_group, criterion_main};
+use stacked_set::AllocHash;
+
+mod common;
+
+criterion_group! {
+    name = contains;
+    config = Criterion::default().sample_size(2000).warm_up_time(Duration::from_millis(500)).measurement_time(Duration::from_secs(5)).with_plots();
+    targets = contains_bench::<AllocHash<'static, TypeId>>
+}
+criterion_group! {
+    name = extend;
+    config = Criterion::default().warm_up_time(Duration::from_millis(500)).measurement_time(Duration::from_secs(2));
+    targets = extend_bench::<AllocHash<'static, usize>>
+}
+criterion_main!(contains, extend);
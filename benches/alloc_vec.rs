@@ -2,7 +2,7 @@
 
 use std::{any::TypeId, time::Duration};
 
-use common::contains_bench;
+use common::{contains_bench, extend_bench};
 use criterion::{Criterion, criterion_group, criterion_main};
 use stacked_set::AllocVec;
 
@@ -13,4 +13,9 @@ criterion_group! {
     config = Criterion::default().sample_size(2000).warm_up_time(Duration::from_millis(500)).measurement_time(Duration::from_secs(5)).with_plots();
     targets = contains_bench::<AllocVec<'static, TypeId>>
 }
-criterion_main!(contains);
+criterion_group! {
+    name = extend;
+    config = Criterion::default().warm_up_time(Duration::from_millis(500)).measurement_time(Duration::from_secs(2));
+    targets = extend_bench::<AllocVec<'static, usize>>
+}
+criterion_main!(contains, extend);
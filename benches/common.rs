@@ -75,3 +75,36 @@ pub fn contains_bench<S: StackedSet<Item = TypeId>>(c: &mut Criterion) {
     );
     bench!(stack 50 c | 0 50 51);
 }
+
+/// Compares extending with and without membership probe
+pub fn extend_bench<S: StackedSet<Item = usize>>(c: &mut Criterion) {
+    fn recurse<S: StackedSet<Item = usize>>(set: &mut S, depth: usize, unique: bool) -> usize {
+        if depth == 0 {
+            return set.len();
+        }
+        let mut set = if unique {
+            set.extend_unique(depth)
+        } else {
+            set.extend(depth)
+        };
+        recurse(&mut set, depth - 1, unique)
+    }
+
+    for depth in [10, 50, 200] {
+        for unique in [false, true] {
+            let method = if unique { "extend_unique" } else { "extend" };
+            c.bench_function(
+                &format!("{method}_d{depth}_r:{}", core::any::type_name::<S>()),
+                |b| {
+                    b.iter(|| {
+                        core::hint::black_box(recurse(
+                            &mut S::empty(),
+                            core::hint::black_box(depth),
+                            unique,
+                        ));
+                    });
+                },
+            );
+        }
+    }
+}
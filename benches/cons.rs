@@ -2,15 +2,20 @@
 
 use std::{any::TypeId, time::Duration};
 
-use common::contains_bench;
+use common::{contains_bench, extend_bench};
 use criterion::{Criterion, criterion_group, criterion_main};
-use stacked_set::StackCons;
+use stacked_set::{StackBloomCons, StackCons};
 
 mod common;
 
 criterion_group! {
     name = contains;
     config = Criterion::default().sample_size(2000).warm_up_time(Duration::from_millis(500)).measurement_time(Duration::from_secs(5)).with_plots();
-    targets = contains_bench::<StackCons<'static, TypeId>>
+    targets = contains_bench::<StackCons<'static, TypeId>>, contains_bench::<StackBloomCons<'static, TypeId>>
 }
-criterion_main!(contains);
+criterion_group! {
+    name = extend;
+    config = Criterion::default().warm_up_time(Duration::from_millis(500)).measurement_time(Duration::from_secs(2));
+    targets = extend_bench::<StackCons<'static, usize>>, extend_bench::<StackBloomCons<'static, usize>>
+}
+criterion_main!(contains, extend);
@@ -0,0 +1,78 @@
+// Generic implementations of set-algebra queries, shared between `StackedSet` default methods and `SetCollection` fast paths
+
+use core::cmp::Ordering;
+
+use crate::{Lookup, StackedSet};
+
+/// Decides if it's cheaper to iterate over `a` and probe `b`, than the other way around
+#[inline]
+fn probe_second<A: StackedSet, B: StackedSet>(a: &A, b: &B) -> bool {
+    #[inline]
+    fn rank(lookup: Lookup) -> u8 {
+        match lookup {
+            Lookup::Linear => 0,
+            Lookup::Sorted => 1,
+            Lookup::Hashed => 2,
+        }
+    }
+
+    match rank(a.lookup()).cmp(&rank(b.lookup())) {
+        Ordering::Less => true,
+        Ordering::Greater => false,
+        Ordering::Equal => a.len() <= b.len(),
+    }
+}
+
+#[inline]
+pub(crate) fn is_subset<A: StackedSet, B: StackedSet<Item = A::Item>>(a: &A, b: &B) -> bool {
+    a.len() <= b.len() && a.iter().all(|item| b.contains_ref(item))
+}
+
+#[inline]
+pub(crate) fn is_disjoint<A: StackedSet, B: StackedSet<Item = A::Item>>(a: &A, b: &B) -> bool {
+    if probe_second(a, b) {
+        !a.iter().any(|item| b.contains_ref(item))
+    } else {
+        !b.iter().any(|item| a.contains_ref(item))
+    }
+}
+
+/// Merge-walk version of [`is_subset`], both iterators must be sorted in ascending order
+#[cfg(any(feature = "alloc-tree", feature = "alloc-sorted"))]
+#[inline]
+pub(crate) fn is_subset_sorted<'a, T: Ord + 'a>(
+    a: impl IntoIterator<Item = &'a T>,
+    b: impl IntoIterator<Item = &'a T>,
+) -> bool {
+    let mut b = b.into_iter();
+    'outer: for a in a {
+        for b in b.by_ref() {
+            match b.cmp(a) {
+                Ordering::Less => {}
+                Ordering::Equal => continue 'outer,
+                Ordering::Greater => return false,
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// Merge-walk version of [`is_disjoint`], both iterators must be sorted in ascending order
+#[cfg(any(feature = "alloc-tree", feature = "alloc-sorted"))]
+#[inline]
+pub(crate) fn is_disjoint_sorted<'a, T: Ord + 'a>(
+    a: impl IntoIterator<Item = &'a T>,
+    b: impl IntoIterator<Item = &'a T>,
+) -> bool {
+    let (mut a, mut b) = (a.into_iter(), b.into_iter());
+    let (mut next_a, mut next_b) = (a.next(), b.next());
+    while let (Some(x), Some(y)) = (next_a, next_b) {
+        match x.cmp(y) {
+            Ordering::Less => next_a = a.next(),
+            Ordering::Greater => next_b = b.next(),
+            Ordering::Equal => return false,
+        }
+    }
+    true
+}
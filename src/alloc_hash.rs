@@ -0,0 +1,121 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use hashbrown::{DefaultHashBuilder, Equivalent, HashSet};
+
+use crate::{
+    Lookup, Query,
+    collection::{CollectionSet, SetCollection},
+};
+
+/// [`hashbrown::HashSet`]-based implementation, requiring no `std`
+///
+/// Uses [`hashbrown`]'s default hasher, see [`AllocHashWith`](crate::AllocHashWith) to pick another one
+pub type Hash<'l, Item> = HashWith<'l, Item, DefaultHashBuilder>;
+
+/// [`hashbrown::HashSet`]-based implementation, hashing items with `S`. Any [`BuildHasher`] constructible with [`Default`] will do, for example the ones provided by `foldhash` or `ahash` crates
+///
+/// [`StackedSet::get`](crate::StackedSet::get) looks items up by any [`Equivalent`] form, not necessarily the borrowed one - for example, a tuple of references can be used to query a set of owned tuples
+pub type HashWith<'l, Item, S> = CollectionSet<'l, HashSet<Item, S>>;
+
+impl<Item: Clone + Eq + core::hash::Hash, S: BuildHasher + Default> SetCollection
+    for HashSet<Item, S>
+{
+    type Item = Item;
+
+    type ExtendMemory = Item;
+
+    type ExtendManyMemory = Vec<Item>;
+
+    #[inline]
+    fn new() -> Self {
+        Self::with_hasher(S::default())
+    }
+
+    #[inline]
+    fn extend(&mut self, new_item: Self::Item) -> Self::ExtendMemory {
+        self.insert(new_item.clone());
+        new_item
+    }
+
+    #[inline]
+    fn try_extend(&mut self, new_item: Self::Item) -> Option<Self::ExtendMemory> {
+        // single lookup, as insertion reports item presence anyway
+        HashSet::insert(self, new_item.clone()).then_some(new_item)
+    }
+
+    #[inline]
+    fn extend_many(
+        &mut self,
+        new_items: impl IntoIterator<Item = Self::Item>,
+    ) -> Self::ExtendManyMemory {
+        new_items
+            .into_iter()
+            .filter(|new_item| self.insert(new_item.clone()))
+            .collect()
+    }
+
+    #[inline]
+    fn contains_ref(&self, item: &Self::Item) -> bool {
+        HashSet::contains(self, item)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        HashSet::len(self)
+    }
+
+    #[inline]
+    fn remove(&mut self, present_item: Self::ExtendMemory) {
+        HashSet::remove(self, &present_item);
+    }
+
+    #[inline]
+    fn remove_many(&mut self, present_items: Self::ExtendManyMemory) {
+        for present_item in present_items {
+            HashSet::remove(self, &present_item);
+        }
+    }
+
+    #[inline]
+    fn extended_item<'a>(&'a self, memory: &'a Self::ExtendMemory) -> &'a Self::Item {
+        memory
+    }
+
+    type Added<'i>
+        = core::slice::Iter<'i, Item>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn extended_items<'a>(&'a self, memory: &'a Self::ExtendManyMemory) -> Self::Added<'a> {
+        memory.iter()
+    }
+
+    const LOOKUP: Lookup = Lookup::Hashed;
+
+    type IntoIter<'i>
+        = hashbrown::hash_set::Iter<'i, Item>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn iter(&self) -> Self::IntoIter<'_> {
+        HashSet::iter(self)
+    }
+}
+
+impl<Item, S, Q> Query<HashSet<Item, S>> for Q
+where
+    Item: Clone + Eq + core::hash::Hash,
+    S: BuildHasher + Default,
+    Q: ?Sized + core::hash::Hash + Equivalent<Item>,
+{
+    #[inline]
+    fn get_in<'a>(&self, collection: &'a HashSet<Item, S>) -> Option<&'a Item>
+    where
+        HashSet<Item, S>: 'a,
+    {
+        HashSet::get(collection, self)
+    }
+}
@@ -0,0 +1,273 @@
+use alloc::vec::Vec;
+use core::{
+    borrow::Borrow,
+    ops::{Bound, Range, RangeBounds},
+};
+
+use crate::{Lookup, Query, StackedSet, algebra, collection::SetCollection};
+
+/// Sorted [`alloc::vec::Vec`]-based implementation, looking items up with binary search
+///
+/// Unlike [`AllocTree`](crate::AllocTree), items are never cloned, and are stored contiguously
+pub type Sorted<'l, Item> = crate::collection::CollectionSet<'l, SortedVec<Item>>;
+
+/// Sorted vector of items, implementing [`SetCollection`]
+///
+/// Along with the items, current index of every one of them is kept in order they were added, so that [`SetCollection::ExtendMemory`] is just a position in that list. Frames are dropped in reverse order they were created, so by the time an item is removed, only the committed items (see [`CollectionSet::commit`](crate::collection::CollectionSet::commit)) could be added after it
+#[derive(Debug, Clone)]
+pub struct SortedVec<Item> {
+    items: Vec<Item>,
+    // index of each item within `items`, in order they were added
+    positions: Vec<usize>,
+}
+
+impl<Item> SortedVec<Item> {
+    /// Returns the items in ascending order
+    #[inline]
+    #[must_use]
+    pub fn as_slice(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// Inserts the item at `index`, which must keep the items sorted. Returns its position in order of addition
+    #[inline]
+    fn insert_at(&mut self, index: usize, new_item: Item) -> usize {
+        self.items.insert(index, new_item);
+        for position in &mut self.positions {
+            if *position >= index {
+                *position += 1;
+            }
+        }
+        self.positions.push(index);
+        self.positions.len() - 1
+    }
+
+    /// Removes the item at `added` position in order of addition
+    #[inline]
+    fn remove_added(&mut self, added: usize) {
+        let index = self.positions.remove(added);
+        let _ = self.items.remove(index);
+        for position in &mut self.positions {
+            if *position > index {
+                *position -= 1;
+            }
+        }
+    }
+}
+
+impl<Item: Ord> SortedVec<Item> {
+    /// Index of the item within the slice, or the one it should be inserted at
+    #[inline]
+    fn search<Q>(&self, item: &Q) -> Result<usize, usize>
+    where
+        Item: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        self.items.binary_search_by(|this| this.borrow().cmp(item))
+    }
+}
+
+impl<Item: Ord> SetCollection for SortedVec<Item> {
+    type Item = Item;
+
+    type ExtendMemory = usize;
+
+    // items added at once are contiguous in order of addition, even though they are scattered across the vector
+    type ExtendManyMemory = Range<usize>;
+
+    #[inline]
+    fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            positions: Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn extend(&mut self, new_item: Self::Item) -> Self::ExtendMemory {
+        let (Ok(index) | Err(index)) = self.search(&new_item);
+        self.insert_at(index, new_item)
+    }
+
+    #[inline]
+    fn try_extend(&mut self, new_item: Self::Item) -> Option<Self::ExtendMemory> {
+        // single lookup, as the search reports item presence anyway
+        let index = self.search(&new_item).err()?;
+        Some(self.insert_at(index, new_item))
+    }
+
+    #[inline]
+    fn extend_many(
+        &mut self,
+        new_items: impl IntoIterator<Item = Self::Item>,
+    ) -> Self::ExtendManyMemory {
+        let start = self.positions.len();
+        for new_item in new_items {
+            if let Err(index) = self.search(&new_item) {
+                self.insert_at(index, new_item);
+            }
+        }
+        start..self.positions.len()
+    }
+
+    #[inline]
+    fn contains_ref(&self, item: &Self::Item) -> bool {
+        self.search(item).is_ok()
+    }
+
+    #[inline]
+    fn find_in_range<Q, R>(
+        &self,
+        range: R,
+        mut predicate: impl FnMut(&Self::Item) -> bool,
+    ) -> Option<&Self::Item>
+    where
+        Item: Borrow<Q>,
+        Q: ?Sized + Ord,
+        R: RangeBounds<Q>,
+    {
+        // items are sorted, so only the ones within the range are visited
+        let start = match range.start_bound() {
+            Bound::Included(start) => self.items.partition_point(|this| this.borrow() < start),
+            Bound::Excluded(start) => self.items.partition_point(|this| this.borrow() <= start),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => self.items.partition_point(|this| this.borrow() <= end),
+            Bound::Excluded(end) => self.items.partition_point(|this| this.borrow() < end),
+            Bound::Unbounded => self.items.len(),
+        };
+        <[Item]>::get(&self.items, start..end)?
+            .iter()
+            .find(|item| predicate(item))
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    #[inline]
+    fn remove(&mut self, present_item: Self::ExtendMemory) {
+        self.remove_added(present_item);
+    }
+
+    #[inline]
+    fn remove_many(&mut self, present_items: Self::ExtendManyMemory) {
+        for added in present_items.rev() {
+            self.remove_added(added);
+        }
+    }
+
+    #[inline]
+    fn extended_item<'a>(&'a self, memory: &'a Self::ExtendMemory) -> &'a Self::Item {
+        &self.items[self.positions[*memory]]
+    }
+
+    type Added<'i>
+        = AddedIter<'i, Item>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn extended_items<'a>(&'a self, memory: &'a Self::ExtendManyMemory) -> Self::Added<'a> {
+        AddedIter {
+            items: &self.items,
+            positions: self.positions[memory.clone()].iter(),
+        }
+    }
+
+    #[inline]
+    fn added(&self) -> Option<Self::Added<'_>> {
+        Some(AddedIter {
+            items: &self.items,
+            positions: self.positions.iter(),
+        })
+    }
+
+    const LOOKUP: Lookup = Lookup::Sorted;
+
+    #[inline]
+    fn is_subset_of_fast(&self, other: &impl StackedSet<Item = Self::Item>) -> Option<bool> {
+        // both sets are sorted, so merge-walk them
+        (other.lookup() == Lookup::Sorted).then(|| {
+            self.items.len() <= other.len()
+                && algebra::is_subset_sorted(self.items.iter(), other.iter())
+        })
+    }
+
+    #[inline]
+    fn is_disjoint_fast(&self, other: &impl StackedSet<Item = Self::Item>) -> Option<bool> {
+        // both sets are sorted, so merge-walk them
+        (other.lookup() == Lookup::Sorted)
+            .then(|| algebra::is_disjoint_sorted(self.items.iter(), other.iter()))
+    }
+
+    type IntoIter<'i>
+        = core::slice::Iter<'i, Item>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn iter(&self) -> Self::IntoIter<'_> {
+        self.items.iter()
+    }
+}
+
+impl<Item: Ord + Borrow<Q>, Q: ?Sized + Ord> Query<SortedVec<Item>> for Q {
+    #[inline]
+    fn get_in<'a>(&self, collection: &'a SortedVec<Item>) -> Option<&'a Item>
+    where
+        Item: 'a,
+    {
+        collection
+            .search(self)
+            .ok()
+            .map(|index| &collection.items[index])
+    }
+}
+
+/// Iterator over [`SortedVec`] items in order they were added
+#[allow(missing_debug_implementations)]
+pub struct AddedIter<'i, Item> {
+    items: &'i [Item],
+    positions: core::slice::Iter<'i, usize>,
+}
+
+impl<Item> Clone for AddedIter<'_, Item> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items,
+            positions: self.positions.clone(),
+        }
+    }
+}
+
+impl<'i, Item> Iterator for AddedIter<'i, Item> {
+    type Item = &'i Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.positions.next().map(|&index| &self.items[index])
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.positions.nth(n).map(|&index| &self.items[index])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.positions.size_hint()
+    }
+}
+
+impl<Item> DoubleEndedIterator for AddedIter<'_, Item> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.positions.next_back().map(|&index| &self.items[index])
+    }
+}
+
+impl<Item> ExactSizeIterator for AddedIter<'_, Item> {}
@@ -1,6 +1,10 @@
-use alloc::collections::BTreeSet;
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::{borrow::Borrow, ops::RangeBounds};
 
-use crate::collection::SetCollection;
+use crate::{
+    Lookup, Query, StackedSet, algebra, collection::SetCollection, collection_bag::BagCollection,
+    collection_map::MapCollection,
+};
 
 /// [`alloc::collections::BTreeSet`]-based implementation
 ///
@@ -18,6 +22,8 @@ impl<Item: Ord + PartialEq + Clone> SetCollection for BTreeSet<Item> {
 
     type ExtendMemory = Item;
 
+    type ExtendManyMemory = alloc::vec::Vec<Item>;
+
     #[inline]
     fn new() -> Self {
         Self::new()
@@ -29,16 +35,94 @@ impl<Item: Ord + PartialEq + Clone> SetCollection for BTreeSet<Item> {
         new_item
     }
 
+    #[inline]
+    fn try_extend(&mut self, new_item: Self::Item) -> Option<Self::ExtendMemory> {
+        // single lookup, as insertion reports item presence anyway
+        alloc::collections::BTreeSet::insert(self, new_item.clone()).then_some(new_item)
+    }
+
+    #[inline]
+    fn extend_many(
+        &mut self,
+        new_items: impl IntoIterator<Item = Self::Item>,
+    ) -> Self::ExtendManyMemory {
+        new_items
+            .into_iter()
+            .filter(|new_item| self.insert(new_item.clone()))
+            .collect()
+    }
+
     #[inline]
     fn contains_ref(&self, item: &Self::Item) -> bool {
         alloc::collections::BTreeSet::contains(self, item)
     }
 
+    #[inline]
+    fn find_in_range<Q, R>(
+        &self,
+        range: R,
+        mut predicate: impl FnMut(&Self::Item) -> bool,
+    ) -> Option<&Self::Item>
+    where
+        Item: Borrow<Q>,
+        Q: ?Sized + Ord,
+        R: RangeBounds<Q>,
+    {
+        // items are sorted, so only the ones within the range are visited
+        alloc::collections::BTreeSet::range(self, range).find(|item| predicate(item))
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        alloc::collections::BTreeSet::len(self)
+    }
+
     #[inline]
     fn remove(&mut self, present_item: Self::ExtendMemory) {
         alloc::collections::BTreeSet::remove(self, &present_item);
     }
 
+    #[inline]
+    fn remove_many(&mut self, present_items: Self::ExtendManyMemory) {
+        for present_item in present_items {
+            alloc::collections::BTreeSet::remove(self, &present_item);
+        }
+    }
+
+    #[inline]
+    fn extended_item<'a>(&'a self, memory: &'a Self::ExtendMemory) -> &'a Self::Item {
+        memory
+    }
+
+    type Added<'i>
+        = core::slice::Iter<'i, Item>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn extended_items<'a>(&'a self, memory: &'a Self::ExtendManyMemory) -> Self::Added<'a> {
+        memory.iter()
+    }
+
+    const LOOKUP: Lookup = Lookup::Sorted;
+
+    #[inline]
+    fn is_subset_of_fast(&self, other: &impl StackedSet<Item = Self::Item>) -> Option<bool> {
+        // both sets are sorted, so merge-walk them
+        (other.lookup() == Lookup::Sorted).then(|| {
+            alloc::collections::BTreeSet::len(self) <= other.len()
+                && algebra::is_subset_sorted(alloc::collections::BTreeSet::iter(self), other.iter())
+        })
+    }
+
+    #[inline]
+    fn is_disjoint_fast(&self, other: &impl StackedSet<Item = Self::Item>) -> Option<bool> {
+        // both sets are sorted, so merge-walk them
+        (other.lookup() == Lookup::Sorted).then(|| {
+            algebra::is_disjoint_sorted(alloc::collections::BTreeSet::iter(self), other.iter())
+        })
+    }
+
     type IntoIter<'i>
         = alloc::collections::btree_set::Iter<'i, Item>
     where
@@ -49,3 +133,119 @@ impl<Item: Ord + PartialEq + Clone> SetCollection for BTreeSet<Item> {
         alloc::collections::BTreeSet::iter(self)
     }
 }
+
+impl<Item: Ord + Clone + Borrow<Q>, Q: ?Sized + Ord> Query<BTreeSet<Item>> for Q {
+    #[inline]
+    fn get_in<'a>(&self, collection: &'a BTreeSet<Item>) -> Option<&'a Item>
+    where
+        Item: 'a,
+    {
+        BTreeSet::get(collection, self)
+    }
+}
+
+/// [`alloc::collections::BTreeMap`]-based implementation of [`StackedMap`](crate::StackedMap)
+pub type TreeMap<'l, Key, Value> =
+    crate::collection_map::CollectionMap<'l, alloc::collections::BTreeMap<Key, Value>>;
+
+impl<Key: Ord + Clone, Value> MapCollection for BTreeMap<Key, Value> {
+    type Key = Key;
+
+    type Value = Value;
+
+    type InsertMemory = (Key, Option<Value>);
+
+    #[inline]
+    fn new() -> Self {
+        Self::new()
+    }
+
+    #[inline]
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Self::InsertMemory {
+        let shadowed = alloc::collections::BTreeMap::insert(self, key.clone(), value);
+        (key, shadowed)
+    }
+
+    #[inline]
+    fn get_ref(&self, key: &Self::Key) -> Option<&Self::Value> {
+        alloc::collections::BTreeMap::get(self, key)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        alloc::collections::BTreeMap::len(self)
+    }
+
+    #[inline]
+    fn remove(&mut self, (key, shadowed): Self::InsertMemory) {
+        match shadowed {
+            Some(value) => {
+                alloc::collections::BTreeMap::insert(self, key, value);
+            }
+            None => {
+                alloc::collections::BTreeMap::remove(self, &key);
+            }
+        }
+    }
+
+    type IntoIter<'i>
+        = alloc::collections::btree_map::Iter<'i, Key, Value>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn iter(&self) -> Self::IntoIter<'_> {
+        alloc::collections::BTreeMap::iter(self)
+    }
+}
+
+/// [`alloc::collections::BTreeMap`]-based implementation of [`StackedBag`](crate::StackedBag)
+pub type TreeBag<'l, Item> =
+    crate::collection_bag::CollectionBag<'l, alloc::collections::BTreeMap<Item, usize>>;
+
+impl<Item: Ord + Clone> BagCollection for BTreeMap<Item, usize> {
+    type Item = Item;
+
+    type ExtendMemory = Item;
+
+    #[inline]
+    fn new() -> Self {
+        Self::new()
+    }
+
+    #[inline]
+    fn extend(&mut self, new_item: Self::Item) -> Self::ExtendMemory {
+        *self.entry(new_item.clone()).or_insert(0) += 1;
+        new_item
+    }
+
+    #[inline]
+    fn count_ref(&self, item: &Self::Item) -> usize {
+        alloc::collections::BTreeMap::get(self, item)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    #[inline]
+    fn remove(&mut self, present_item: Self::ExtendMemory) {
+        if let Some(count) = alloc::collections::BTreeMap::get_mut(self, &present_item) {
+            *count -= 1;
+            if *count == 0 {
+                alloc::collections::BTreeMap::remove(self, &present_item);
+            }
+        }
+    }
+
+    type IntoIter<'i>
+        = core::iter::Map<
+        alloc::collections::btree_map::Iter<'i, Item, usize>,
+        fn((&'i Item, &'i usize)) -> (&'i Item, usize),
+    >
+    where
+        Self: 'i;
+
+    #[inline]
+    fn iter(&self) -> Self::IntoIter<'_> {
+        alloc::collections::BTreeMap::iter(self).map(|(item, count)| (item, *count))
+    }
+}
@@ -1,4 +1,6 @@
-use crate::collection::SetCollection;
+use core::borrow::Borrow;
+
+use crate::{Query, collection::SetCollection};
 
 /// [`alloc::vec::Vec`]-based implementation
 ///
@@ -8,7 +10,9 @@ pub type Vec<'l, Item> = crate::collection::CollectionSet<'l, alloc::vec::Vec<It
 impl<Item: PartialEq> SetCollection for alloc::vec::Vec<Item> {
     type Item = Item;
 
-    type ExtendMemory = ();
+    type ExtendMemory = usize;
+
+    type ExtendManyMemory = core::ops::Range<usize>;
 
     #[inline]
     fn new() -> Self {
@@ -18,6 +22,31 @@ impl<Item: PartialEq> SetCollection for alloc::vec::Vec<Item> {
     #[inline]
     fn extend(&mut self, new_item: Self::Item) -> Self::ExtendMemory {
         self.push(new_item);
+        self.len() - 1
+    }
+
+    #[inline]
+    fn extend_many(
+        &mut self,
+        new_items: impl IntoIterator<Item = Self::Item>,
+    ) -> Self::ExtendManyMemory {
+        let len = self.len();
+        for new_item in new_items {
+            if !<[Item]>::contains(self, &new_item) {
+                self.push(new_item);
+            }
+        }
+        len..self.len()
+    }
+
+    #[inline]
+    fn extend_many_unique(
+        &mut self,
+        new_items: impl IntoIterator<Item = Self::Item>,
+    ) -> Self::ExtendManyMemory {
+        let len = self.len();
+        Extend::extend(self, new_items);
+        len..self.len()
     }
 
     #[inline]
@@ -26,8 +55,39 @@ impl<Item: PartialEq> SetCollection for alloc::vec::Vec<Item> {
     }
 
     #[inline]
-    fn remove(&mut self, _present_item: Self::ExtendMemory) {
-        let _ = self.pop();
+    fn len(&self) -> usize {
+        alloc::vec::Vec::len(self)
+    }
+
+    #[inline]
+    fn remove(&mut self, present_item: Self::ExtendMemory) {
+        // items added later are only present if committed, so this is usually just a pop
+        let _ = alloc::vec::Vec::remove(self, present_item);
+    }
+
+    #[inline]
+    fn remove_many(&mut self, present_items: Self::ExtendManyMemory) {
+        let _ = self.drain(present_items);
+    }
+
+    #[inline]
+    fn extended_item<'a>(&'a self, memory: &'a Self::ExtendMemory) -> &'a Self::Item {
+        &self[*memory]
+    }
+
+    type Added<'i>
+        = core::slice::Iter<'i, Item>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn extended_items<'a>(&'a self, memory: &'a Self::ExtendManyMemory) -> Self::Added<'a> {
+        self[memory.clone()].iter()
+    }
+
+    #[inline]
+    fn added(&self) -> Option<Self::Added<'_>> {
+        Some(<[Item]>::iter(self))
     }
 
     type IntoIter<'i>
@@ -40,3 +100,13 @@ impl<Item: PartialEq> SetCollection for alloc::vec::Vec<Item> {
         <[Item]>::iter(self)
     }
 }
+
+impl<Item: PartialEq + Borrow<Q>, Q: ?Sized + PartialEq> Query<alloc::vec::Vec<Item>> for Q {
+    #[inline]
+    fn get_in<'a>(&self, collection: &'a alloc::vec::Vec<Item>) -> Option<&'a Item>
+    where
+        Item: 'a,
+    {
+        <[Item]>::iter(collection).find(|this| (*this).borrow() == self)
+    }
+}
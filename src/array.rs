@@ -0,0 +1,31 @@
+use crate::collection::{Buffer, BufferCollection};
+
+/// Fixed-capacity array-based implementation, requiring neither `alloc`, nor walking a list through stack frames
+///
+/// # Panics
+///
+/// [`StackedSet`](crate::StackedSet) methods panic once the set is full, as frames can't fail to be created. Whenever the input is not known to fit, [`CollectionSet::checked_extend`](crate::collection::CollectionSet::checked_extend), [`CollectionSet::checked_try_extend`](crate::collection::CollectionSet::checked_try_extend) and [`CollectionSet::checked_extend_many`](crate::collection::CollectionSet::checked_extend_many) are the only way to extend the set without risking a panic
+pub type Array<'l, Item, const N: usize> =
+    crate::collection::CollectionSet<'l, ArrayCollection<Item, N>>;
+
+/// Inline buffer of at most `N` items, implementing [`SetCollection`](crate::collection::SetCollection)
+pub type ArrayCollection<Item, const N: usize> = BufferCollection<[Option<Item>; N]>;
+
+impl<Item, const N: usize> Buffer for [Option<Item>; N] {
+    type Item = Item;
+
+    #[inline]
+    fn new() -> Self {
+        core::array::from_fn(|_| None)
+    }
+
+    #[inline]
+    fn slots(&self) -> &[Option<Self::Item>] {
+        self
+    }
+
+    #[inline]
+    fn slots_mut(&mut self) -> &mut [Option<Self::Item>] {
+        self
+    }
+}
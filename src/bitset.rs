@@ -0,0 +1,337 @@
+use crate::{Lookup, Query, collection::SetCollection};
+
+/// Maps items to bit indices, so that they could be stored in [`BitCollection`]. Distinct items must map to distinct indices, and indices should be small, as bitset size is proportional to the largest one
+///
+/// Types items are looked up by (see [`StackedSet::get`](crate::StackedSet::get)) implement it as well, mapping to the index of the item they are equal to
+///
+/// Implemented for unsigned integers. Use [`bit_index_enum`](crate::bit_index_enum) macro for fieldless enums
+pub trait BitIndex {
+    /// Returns bit index of the item
+    fn bit_index(&self) -> usize;
+}
+
+macro_rules! impl_bit_index {
+    ($($ty:ty),*) => {
+        $(impl BitIndex for $ty {
+            #[inline]
+            fn bit_index(&self) -> usize {
+                usize::from(*self)
+            }
+        })*
+    };
+}
+
+impl_bit_index!(u8, u16, usize);
+
+impl BitIndex for u32 {
+    #[inline]
+    fn bit_index(&self) -> usize {
+        usize::try_from(*self).expect("Bit index does not fit into usize")
+    }
+}
+
+/// Defines a fieldless enum along with [`BitIndex`] implementation, mapping each variant to its discriminant
+///
+/// ```rust
+/// # use stacked_set::{BitIndex, bit_index_enum};
+/// bit_index_enum! {
+///     #[derive(Debug, PartialEq)]
+///     pub enum Color {
+///         Red,
+///         Green = 5,
+///         Blue,
+///     }
+/// }
+/// assert_eq!(Color::Red.bit_index(), 0);
+/// assert_eq!(Color::Blue.bit_index(), 6);
+/// ```
+#[macro_export]
+macro_rules! bit_index_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident $(= $discriminant:expr)?),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($(#[$variant_meta])* $variant $(= $discriminant)?),*
+        }
+
+        impl $crate::BitIndex for $name {
+            #[inline]
+            fn bit_index(&self) -> usize {
+                match self {
+                    $(Self::$variant => Self::$variant as usize),*
+                }
+            }
+        }
+    };
+}
+
+/// Storage of the bits for [`BitCollection`]. Implemented for fixed `[u64; W]` arrays and, with `alloc` feature, for growable `Vec<u64>`
+pub trait BitStorage {
+    /// Creates storage with all bits cleared
+    fn new() -> Self;
+
+    /// Checks if the bit is set. Bits out of storage range are never set
+    fn get(&self, index: usize) -> bool;
+
+    /// Makes room for the bit, panicking if storage can't hold it
+    fn reserve(&mut self, index: usize);
+
+    /// Sets the bit, panicking if storage can't hold it
+    fn set(&mut self, index: usize);
+
+    /// Clears the bit
+    fn clear(&mut self, index: usize);
+}
+
+impl<const W: usize> BitStorage for [u64; W] {
+    #[inline]
+    fn new() -> Self {
+        [0; W]
+    }
+
+    #[inline]
+    fn get(&self, index: usize) -> bool {
+        <[u64]>::get(self, index / 64).is_some_and(|word| word & (1 << (index % 64)) != 0)
+    }
+
+    #[inline]
+    fn reserve(&mut self, index: usize) {
+        assert!(
+            index / 64 < W,
+            "Bit index {index} is out of bitset range of {}",
+            W * 64
+        );
+    }
+
+    #[inline]
+    fn set(&mut self, index: usize) {
+        self.reserve(index);
+        self[index / 64] |= 1 << (index % 64);
+    }
+
+    #[inline]
+    fn clear(&mut self, index: usize) {
+        if let Some(word) = <[u64]>::get_mut(self, index / 64) {
+            *word &= !(1 << (index % 64));
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BitStorage for alloc::vec::Vec<u64> {
+    #[inline]
+    fn new() -> Self {
+        alloc::vec::Vec::new()
+    }
+
+    #[inline]
+    fn get(&self, index: usize) -> bool {
+        <[u64]>::get(self, index / 64).is_some_and(|word| word & (1 << (index % 64)) != 0)
+    }
+
+    #[inline]
+    fn reserve(&mut self, index: usize) {
+        if index / 64 >= self.len() {
+            self.resize(index / 64 + 1, 0);
+        }
+    }
+
+    #[inline]
+    fn set(&mut self, index: usize) {
+        self.reserve(index);
+        self[index / 64] |= 1 << (index % 64);
+    }
+
+    #[inline]
+    fn clear(&mut self, index: usize) {
+        if let Some(word) = <[u64]>::get_mut(self, index / 64) {
+            *word &= !(1 << (index % 64));
+        }
+    }
+}
+
+/// Panics, reporting the storage capacity exceeded
+#[cold]
+#[track_caller]
+fn overflow(capacity: usize) -> ! {
+    panic!("Bit set storage capacity of {capacity} exceeded")
+}
+
+/// Bitset-accelerated [`SetCollection`]: membership is a single bit test, while items themselves are kept in push/pop `Storage` collection, so that frames could still be inspected (see [`StackedSet::frames`](crate::StackedSet::frames))
+///
+/// Each frame clears exactly the bit it has set, once dropped. Extending the set with an item out of bits range panics before that item is stored, leaving the set intact (items of the same [`StackedSet::extend_many`](crate::StackedSet::extend_many) call preceding it may stay though). Going over storage capacity panics the same way, see [`CollectionSet::checked_extend`](crate::collection::CollectionSet::checked_extend) to avoid that
+#[derive(Debug, Clone)]
+pub struct BitCollection<Storage, Bits> {
+    storage: Storage,
+    bits: Bits,
+}
+
+impl<Storage, Bits> BitCollection<Storage, Bits> {
+    /// Returns underlying item storage
+    #[inline]
+    #[must_use]
+    pub fn storage(&self) -> &Storage {
+        &self.storage
+    }
+
+    /// Returns underlying bits
+    #[inline]
+    #[must_use]
+    pub fn bits(&self) -> &Bits {
+        &self.bits
+    }
+}
+
+impl<Storage, Bits> SetCollection for BitCollection<Storage, Bits>
+where
+    Storage: SetCollection,
+    Storage::Item: BitIndex,
+    Bits: BitStorage,
+{
+    type Item = Storage::Item;
+
+    type ExtendMemory = Storage::ExtendMemory;
+
+    type ExtendManyMemory = Storage::ExtendManyMemory;
+
+    #[inline]
+    fn new() -> Self {
+        Self {
+            storage: Storage::new(),
+            bits: Bits::new(),
+        }
+    }
+
+    #[inline]
+    fn extend(&mut self, new_item: Self::Item) -> Self::ExtendMemory {
+        let index = new_item.bit_index();
+        // bit is reserved first, so that an index out of range doesn't leave an item stored without its bit
+        self.bits.reserve(index);
+        let memory = self.storage.extend(new_item);
+        self.bits.set(index);
+        memory
+    }
+
+    #[inline]
+    fn extend_many(
+        &mut self,
+        new_items: impl IntoIterator<Item = Self::Item>,
+    ) -> Self::ExtendManyMemory {
+        let capacity = self.storage.capacity();
+        let mut len = self.storage.len();
+        let bits = &mut self.bits;
+        // present and repeated items are filtered out by their bits, so that storage doesn't look up any of them. Bit is set before the item is stored, so overflow is caught here rather than by storage, which would drop the items leaving their bits behind
+        self.storage
+            .extend_many_unique(new_items.into_iter().filter(|new_item| {
+                let index = new_item.bit_index();
+                if bits.get(index) {
+                    return false;
+                }
+                if let Some(capacity) = capacity.filter(|&capacity| len == capacity) {
+                    overflow(capacity);
+                }
+                bits.set(index);
+                len += 1;
+                true
+            }))
+    }
+
+    #[inline]
+    fn contains_ref(&self, item: &Self::Item) -> bool {
+        self.bits.get(item.bit_index())
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    #[inline]
+    fn remove(&mut self, present_item: Self::ExtendMemory) {
+        self.bits
+            .clear(self.storage.extended_item(&present_item).bit_index());
+        self.storage.remove(present_item);
+    }
+
+    #[inline]
+    fn remove_many(&mut self, present_items: Self::ExtendManyMemory) {
+        for item in self.storage.extended_items(&present_items) {
+            self.bits.clear(item.bit_index());
+        }
+        self.storage.remove_many(present_items);
+    }
+
+    #[inline]
+    fn extended_item<'a>(&'a self, memory: &'a Self::ExtendMemory) -> &'a Self::Item {
+        self.storage.extended_item(memory)
+    }
+
+    type Added<'i>
+        = Storage::Added<'i>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn extended_items<'a>(&'a self, memory: &'a Self::ExtendManyMemory) -> Self::Added<'a> {
+        self.storage.extended_items(memory)
+    }
+
+    #[inline]
+    fn added(&self) -> Option<Self::Added<'_>> {
+        self.storage.added()
+    }
+
+    #[inline]
+    fn capacity(&self) -> Option<usize> {
+        self.storage.capacity()
+    }
+
+    const LOOKUP: Lookup = Lookup::Hashed;
+
+    type IntoIter<'i>
+        = Storage::IntoIter<'i>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn iter(&self) -> Self::IntoIter<'_> {
+        self.storage.iter()
+    }
+}
+
+impl<Storage, Bits, Q> Query<BitCollection<Storage, Bits>> for Q
+where
+    Storage: SetCollection,
+    Storage::Item: BitIndex,
+    Bits: BitStorage,
+    Q: ?Sized + BitIndex + Query<Storage>,
+{
+    #[inline]
+    fn get_in<'a>(&self, collection: &'a BitCollection<Storage, Bits>) -> Option<&'a Storage::Item>
+    where
+        BitCollection<Storage, Bits>: 'a,
+    {
+        // storage is only scanned for the item to borrow, once its bit says it's there
+        if !collection.bits.get(self.bit_index()) {
+            return None;
+        }
+        self.get_in(&collection.storage)
+    }
+}
+
+/// Alloc-free bitset-accelerated implementation, holding up to `N` items with bit indices below `W * 64`
+pub type BitArray<'l, Item, const W: usize, const N: usize> = crate::collection::CollectionSet<
+    'l,
+    BitCollection<crate::collection::ArrayCollection<Item, N>, [u64; W]>,
+>;
+
+/// Growable bitset-accelerated implementation
+#[cfg(feature = "alloc-vec")]
+pub type BitVec<'l, Item> = crate::collection::CollectionSet<
+    'l,
+    BitCollection<alloc::vec::Vec<Item>, alloc::vec::Vec<u64>>,
+>;
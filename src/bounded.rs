@@ -0,0 +1,366 @@
+use core::{borrow::Borrow, fmt::Display, ops::RangeBounds};
+
+use crate::{Extended, Lookup, Query, StackedSet};
+
+/// Limits enforced by [`Bounded`] adapter. Implemented by [`ConstLimits`] (limits known at compile time) and [`RuntimeLimits`]
+pub trait Limits: Copy + Default {
+    /// Maximum number of frames a set may consist of, see [`StackedSet::depth`]
+    fn max_depth(&self) -> usize;
+
+    /// Maximum number of items a set may contain, see [`StackedSet::len`]
+    fn max_len(&self) -> usize;
+}
+
+/// [`Limits`] defined with const generics, so that they take no space in [`Bounded`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConstLimits<const MAX_DEPTH: usize, const MAX_LEN: usize>;
+
+impl<const MAX_DEPTH: usize, const MAX_LEN: usize> Limits for ConstLimits<MAX_DEPTH, MAX_LEN> {
+    #[inline]
+    fn max_depth(&self) -> usize {
+        MAX_DEPTH
+    }
+
+    #[inline]
+    fn max_len(&self) -> usize {
+        MAX_LEN
+    }
+}
+
+/// [`Limits`] defined at runtime. Default value imposes no limits at all
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeLimits {
+    /// See [`Limits::max_depth`]
+    pub max_depth: usize,
+    /// See [`Limits::max_len`]
+    pub max_len: usize,
+}
+
+impl Default for RuntimeLimits {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            max_len: usize::MAX,
+        }
+    }
+}
+
+impl Limits for RuntimeLimits {
+    #[inline]
+    fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    #[inline]
+    fn max_len(&self) -> usize {
+        self.max_len
+    }
+}
+
+/// Error returned by [`Bounded`] methods, once a new frame would exceed one of the [`Limits`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitExceeded {
+    /// New frame would be deeper than [`Limits::max_depth`]
+    Depth {
+        /// The limit exceeded
+        limit: usize,
+    },
+    /// New frame would contain more items than [`Limits::max_len`]
+    Len {
+        /// The limit exceeded
+        limit: usize,
+    },
+}
+
+impl Display for LimitExceeded {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LimitExceeded::Depth { limit } => {
+                write!(f, "stacked set depth limit of {limit} exceeded")
+            }
+            LimitExceeded::Len { limit } => {
+                write!(f, "stacked set length limit of {limit} exceeded")
+            }
+        }
+    }
+}
+
+impl core::error::Error for LimitExceeded {}
+
+/// Adapter over any [`StackedSet`], that limits its depth and number of items, e.g. to stop recursion over malicious input long before native stack overflows
+///
+/// Use `checked_*` methods to get a [`LimitExceeded`] error once a limit is reached. [`StackedSet`] implementation is provided as well, so that existing generic code keeps working, but its methods panic instead
+///
+/// # Panics
+///
+/// [`StackedSet::extend`], [`StackedSet::try_extend`], [`StackedSet::extend_unique`], [`StackedSet::extend_many`] and [`StackedSet::fork`] (along with every provided method built on them, like [`StackedSet::enter`]) panic once the new frame would exceed the limits. Only the `checked_*` counterparts ([`Bounded::checked_extend`], [`Bounded::checked_try_extend`], [`Bounded::checked_extend_many`] and [`Bounded::checked_fork`]) report it as an error
+#[derive(Debug)]
+pub struct Bounded<S, L: Limits> {
+    set: S,
+    limits: L,
+}
+
+impl<S: StackedSet, L: Limits> Bounded<S, L> {
+    /// Wraps the set, enforcing provided limits on frames produced from it. The set itself is not checked against the limits
+    #[inline]
+    pub fn new(set: S, limits: L) -> Self {
+        Self { set, limits }
+    }
+
+    /// Returns the wrapped set
+    #[inline]
+    #[must_use]
+    pub fn inner(&self) -> &S {
+        &self.set
+    }
+
+    /// Unwraps the set
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> S {
+        self.set
+    }
+
+    /// Returns the limits enforced
+    #[inline]
+    #[must_use]
+    pub fn limits(&self) -> L {
+        self.limits
+    }
+
+    /// Checks that a new frame adding `new_items` items is within the limits
+    #[inline]
+    fn check(&self, new_items: usize) -> Result<(), LimitExceeded> {
+        let (max_depth, max_len) = (self.limits.max_depth(), self.limits.max_len());
+        if self.set.depth() >= max_depth {
+            Err(LimitExceeded::Depth { limit: max_depth })
+        } else if self.set.len().saturating_add(new_items) > max_len {
+            Err(LimitExceeded::Len { limit: max_len })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Same as [`StackedSet::try_extend`], but fails if the new frame exceeds the limits
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LimitExceeded`] if the new frame would be too deep, or, unless the item is already present, contain too many items
+    #[inline]
+    pub fn checked_try_extend(
+        &mut self,
+        new_item: S::Item,
+    ) -> Result<Extended<Bounded<S::Shorten<'_>, L>>, LimitExceeded> {
+        self.check(0)?;
+        let limits = self.limits;
+        let wrap = |set| Bounded { set, limits };
+        // presence is only known once the set is probed, so the length is checked after the insertion, dropping the frame if it's too long
+        Ok(match self.set.try_extend(new_item) {
+            Extended::Inserted(set) if set.len() > limits.max_len() => {
+                return Err(LimitExceeded::Len {
+                    limit: limits.max_len(),
+                });
+            }
+            Extended::Inserted(set) => Extended::Inserted(wrap(set)),
+            Extended::AlreadyPresent(set) => Extended::AlreadyPresent(wrap(set)),
+        })
+    }
+
+    /// Same as [`StackedSet::extend`], but fails if the new frame exceeds the limits
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LimitExceeded`] under the same conditions as [`Bounded::checked_try_extend`]
+    #[inline]
+    pub fn checked_extend(
+        &mut self,
+        new_item: S::Item,
+    ) -> Result<Bounded<S::Shorten<'_>, L>, LimitExceeded> {
+        self.checked_try_extend(new_item).map(Extended::into_inner)
+    }
+
+    /// Same as [`StackedSet::extend_many`], but fails if the new frame exceeds the limits
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LimitExceeded`] if the new frame would be too deep, or contain too many items
+    #[inline]
+    pub fn checked_extend_many(
+        &mut self,
+        new_items: impl IntoIterator<Item = S::Item>,
+    ) -> Result<Bounded<S::Shorten<'_>, L>, LimitExceeded> {
+        self.check(0)?;
+        let limits = self.limits;
+        // items repeated among the new ones are only known to the set itself, so the frame is created first and then checked
+        let set = self.set.extend_many(new_items);
+        if set.len() > limits.max_len() {
+            return Err(LimitExceeded::Len {
+                limit: limits.max_len(),
+            });
+        }
+        Ok(Bounded { set, limits })
+    }
+
+    /// Same as [`StackedSet::fork`], but fails if the new frame exceeds the depth limit
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LimitExceeded`] if the new frame would be too deep
+    #[inline]
+    pub fn checked_fork(&mut self) -> Result<Bounded<S::Shorten<'_>, L>, LimitExceeded> {
+        self.check(0)?;
+        let limits = self.limits;
+        Ok(Bounded {
+            set: self.set.fork(),
+            limits,
+        })
+    }
+}
+
+impl<S: StackedSet, L: Limits> StackedSet for Bounded<S, L> {
+    type Item = S::Item;
+
+    type Backend = S::Backend;
+
+    #[inline]
+    fn empty() -> Self {
+        Self::new(S::empty(), L::default())
+    }
+
+    #[inline]
+    fn contains_ref(&self, item: &Self::Item) -> bool {
+        self.set.contains_ref(item)
+    }
+
+    #[inline]
+    fn contains_by<Q: ?Sized + Query<Self::Backend>>(&self, item: &Q) -> bool {
+        self.set.contains_by(item)
+    }
+
+    #[inline]
+    fn get<Q: ?Sized + Query<Self::Backend>>(&self, item: &Q) -> Option<&Self::Item> {
+        self.set.get(item)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    #[inline]
+    fn depth(&self) -> usize {
+        self.set.depth()
+    }
+
+    type Shorten<'new>
+        = Bounded<S::Shorten<'new>, L>
+    where
+        Self: 'new;
+
+    /// # Panics
+    ///
+    /// Panics if the new frame exceeds the limits, see [`Bounded::checked_try_extend`]
+    #[inline]
+    fn try_extend(&mut self, new_item: Self::Item) -> Extended<Self::Shorten<'_>> {
+        self.checked_try_extend(new_item)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the new frame exceeds the limits, see [`Bounded::checked_try_extend`]
+    #[inline]
+    fn extend_unique(&mut self, new_item: Self::Item) -> Self::Shorten<'_> {
+        self.check(1).unwrap_or_else(|err| panic!("{err}"));
+        Bounded {
+            set: self.set.extend_unique(new_item),
+            limits: self.limits,
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the new frame exceeds the limits, see [`Bounded::checked_extend_many`]
+    #[inline]
+    fn extend_many(
+        &mut self,
+        new_items: impl IntoIterator<Item = Self::Item>,
+    ) -> Self::Shorten<'_> {
+        self.checked_extend_many(new_items)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the new frame exceeds the depth limit, see [`Bounded::checked_fork`]
+    #[inline]
+    fn fork(&mut self) -> Self::Shorten<'_> {
+        self.checked_fork().unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    type IntoIter<'i>
+        = S::IntoIter<'i>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn iter(&self) -> Self::IntoIter<'_> {
+        self.set.iter()
+    }
+
+    type Frames<'i>
+        = S::Frames<'i>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn frames(&self) -> Self::Frames<'_> {
+        self.set.frames()
+    }
+
+    type Path<'i>
+        = S::Path<'i>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn iter_path(&self) -> Self::Path<'_> {
+        self.set.iter_path()
+    }
+
+    #[inline]
+    fn find(&self, predicate: impl FnMut(&Self::Item) -> bool) -> Option<&Self::Item> {
+        self.set.find(predicate)
+    }
+
+    #[inline]
+    fn find_in_range<Q, R>(
+        &self,
+        range: R,
+        predicate: impl FnMut(&Self::Item) -> bool,
+    ) -> Option<&Self::Item>
+    where
+        Self::Item: Borrow<Q>,
+        Q: ?Sized + Ord,
+        R: RangeBounds<Q>,
+    {
+        self.set.find_in_range(range, predicate)
+    }
+
+    #[inline]
+    fn lookup(&self) -> Lookup {
+        self.set.lookup()
+    }
+
+    #[inline]
+    fn is_subset_of(&self, other: &impl StackedSet<Item = Self::Item>) -> bool {
+        self.set.is_subset_of(other)
+    }
+
+    #[inline]
+    fn is_disjoint(&self, other: &impl StackedSet<Item = Self::Item>) -> bool {
+        self.set.is_disjoint(other)
+    }
+}
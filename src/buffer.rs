@@ -0,0 +1,264 @@
+use core::{borrow::Borrow, ops::Range};
+
+use crate::{Query, collection::SetCollection};
+
+/// Slots a [`BufferCollection`] keeps its items in. Implemented for inline `[Option<Item>; N]` arrays (with `array` feature) and caller-provided `&mut [Option<Item>]` slices (with `slice` feature)
+///
+/// Number of slots is the capacity of the collection, and it's not expected to change
+pub trait Buffer {
+    /// Item stored in the slots
+    type Item;
+
+    /// Creates the slots, all of them vacant. See [`SetCollection::new`]
+    fn new() -> Self;
+
+    /// Returns the slots
+    fn slots(&self) -> &[Option<Self::Item>];
+
+    /// Returns the slots mutably
+    fn slots_mut(&mut self) -> &mut [Option<Self::Item>];
+}
+
+/// Fixed-capacity buffer of items, implementing [`SetCollection`]. Items are pushed and popped within the buffer exactly like [`AllocVec`](crate::AllocVec) does
+///
+/// Occupied slots always precede the vacant ones. Removed items are dropped right away
+#[derive(Debug, Clone)]
+pub struct BufferCollection<B> {
+    slots: B,
+    len: usize,
+}
+
+impl<B: Buffer> BufferCollection<B> {
+    /// Creates an empty collection over the slots, dropping the items they hold
+    #[inline]
+    #[must_use]
+    pub fn with_slots(mut slots: B) -> Self {
+        slots.slots_mut().fill_with(|| None);
+        Self { slots, len: 0 }
+    }
+
+    /// Returns iterator over the items, in order they were added
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> BufferIter<'_, B::Item> {
+        BufferIter(self.slots.slots()[..self.len].iter())
+    }
+
+    /// Panics, reporting the capacity exceeded
+    #[cold]
+    #[track_caller]
+    fn overflow(&self) -> ! {
+        panic!(
+            "Buffer-based set capacity of {} exceeded",
+            self.slots.slots().len()
+        )
+    }
+
+    /// Removes items within the range, shifting the ones after it to the left
+    #[inline]
+    fn remove_range(&mut self, range: Range<usize>) {
+        let removed = range.len();
+        let slots = &mut self.slots.slots_mut()[range.start..self.len];
+        for slot in &mut slots[..removed] {
+            drop(slot.take());
+        }
+        slots.rotate_left(removed);
+        self.len -= removed;
+    }
+}
+
+impl<B> BufferCollection<B>
+where
+    B: Buffer,
+    B::Item: PartialEq,
+{
+    /// Pushes the items after the occupied slots, skipping the present ones if `probe` is set
+    #[inline]
+    fn push_many(
+        &mut self,
+        new_items: impl IntoIterator<Item = B::Item>,
+        probe: bool,
+    ) -> Range<usize> {
+        let start = self.len;
+        for new_item in new_items {
+            if probe && self.contains_ref(&new_item) {
+                continue;
+            }
+            let Some(slot) = self.slots.slots_mut().get_mut(self.len) else {
+                // don't leave the items without a frame to remove them
+                self.remove_range(start..self.len);
+                self.overflow();
+            };
+            *slot = Some(new_item);
+            self.len += 1;
+        }
+        start..self.len
+    }
+}
+
+impl<B> SetCollection for BufferCollection<B>
+where
+    B: Buffer,
+    B::Item: PartialEq,
+{
+    type Item = B::Item;
+
+    type ExtendMemory = usize;
+
+    type ExtendManyMemory = Range<usize>;
+
+    #[inline]
+    fn new() -> Self {
+        Self {
+            slots: B::new(),
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn extend(&mut self, new_item: Self::Item) -> Self::ExtendMemory {
+        let Some(slot) = self.slots.slots_mut().get_mut(self.len) else {
+            self.overflow();
+        };
+        *slot = Some(new_item);
+        self.len += 1;
+        self.len - 1
+    }
+
+    #[inline]
+    fn extend_many(
+        &mut self,
+        new_items: impl IntoIterator<Item = Self::Item>,
+    ) -> Self::ExtendManyMemory {
+        self.push_many(new_items, true)
+    }
+
+    #[inline]
+    fn extend_many_unique(
+        &mut self,
+        new_items: impl IntoIterator<Item = Self::Item>,
+    ) -> Self::ExtendManyMemory {
+        self.push_many(new_items, false)
+    }
+
+    #[inline]
+    fn contains_ref(&self, item: &Self::Item) -> bool {
+        BufferCollection::iter(self).any(|this| this == item)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn remove(&mut self, present_item: Self::ExtendMemory) {
+        self.remove_range(present_item..present_item + 1);
+    }
+
+    #[inline]
+    fn remove_many(&mut self, present_items: Self::ExtendManyMemory) {
+        self.remove_range(present_items);
+    }
+
+    #[inline]
+    fn extended_item<'a>(&'a self, memory: &'a Self::ExtendMemory) -> &'a Self::Item {
+        occupied(&self.slots.slots()[*memory])
+    }
+
+    type Added<'i>
+        = BufferIter<'i, B::Item>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn extended_items<'a>(&'a self, memory: &'a Self::ExtendManyMemory) -> Self::Added<'a> {
+        BufferIter(self.slots.slots()[memory.clone()].iter())
+    }
+
+    #[inline]
+    fn added(&self) -> Option<Self::Added<'_>> {
+        Some(BufferCollection::iter(self))
+    }
+
+    #[inline]
+    fn capacity(&self) -> Option<usize> {
+        Some(self.slots.slots().len())
+    }
+
+    type IntoIter<'i>
+        = BufferIter<'i, B::Item>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn iter(&self) -> Self::IntoIter<'_> {
+        BufferCollection::iter(self)
+    }
+}
+
+impl<B, Q> Query<BufferCollection<B>> for Q
+where
+    B: Buffer,
+    B::Item: PartialEq + Borrow<Q>,
+    Q: ?Sized + PartialEq,
+{
+    #[inline]
+    fn get_in<'a>(&self, collection: &'a BufferCollection<B>) -> Option<&'a B::Item>
+    where
+        BufferCollection<B>: 'a,
+    {
+        BufferCollection::iter(collection).find(|this| (*this).borrow() == self)
+    }
+}
+
+/// Item of a slot known to be occupied
+#[inline]
+fn occupied<Item>(slot: &Option<Item>) -> &Item {
+    slot.as_ref()
+        .expect("Occupied slots always precede the vacant ones")
+}
+
+/// Iterator over [`BufferCollection`] items, in order they were added
+#[allow(missing_debug_implementations)]
+pub struct BufferIter<'i, Item>(core::slice::Iter<'i, Option<Item>>);
+
+impl<Item> Clone for BufferIter<'_, Item> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<'i, Item> Iterator for BufferIter<'i, Item> {
+    type Item = &'i Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(occupied)
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.nth(n).map(occupied)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<Item> DoubleEndedIterator for BufferIter<'_, Item> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(occupied)
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.nth_back(n).map(occupied)
+    }
+}
+
+impl<Item> ExactSizeIterator for BufferIter<'_, Item> {}
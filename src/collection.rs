@@ -1,6 +1,34 @@
-use core::{borrow::Borrow, fmt::Debug, ops::Deref};
+use core::{
+    borrow::Borrow,
+    fmt::Debug,
+    ops::{Deref, RangeBounds},
+};
 
-use crate::StackedSet;
+use crate::{Backend, Extended, Lookup, Query, StackedSet, algebra};
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use crate::journal::{Checkpoint, Journal, StaleCheckpoint};
+
+#[cfg(any(feature = "array", feature = "slice"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "array", feature = "slice"))))]
+pub use crate::buffer::{Buffer, BufferCollection};
+
+#[cfg(feature = "array")]
+#[cfg_attr(docsrs, doc(cfg(feature = "array")))]
+pub use crate::array::ArrayCollection;
+
+#[cfg(feature = "slice")]
+#[cfg_attr(docsrs, doc(cfg(feature = "slice")))]
+pub use crate::slice::SliceCollection;
+
+#[cfg(feature = "bitset")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bitset")))]
+pub use crate::bitset::{BitCollection, BitStorage};
+
+#[cfg(feature = "alloc-sorted")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc-sorted")))]
+pub use crate::alloc_sorted::SortedVec;
 
 /// Kind of interface a "collection" should expose for [`StackedSet`] implementation to be built on top of it.
 ///
@@ -9,7 +37,7 @@ pub trait SetCollection {
     /// Element type stored in the collection.
     type Item;
 
-    /// A some sort of memory that can be used to remove the item back. Some implementations (like `Vec` may want to make this a unit type, if the item to remove is somehow known through collection structure).
+    /// A some sort of memory that can be used to remove the item back. Some implementations (like `Vec`) may want to make this something small (like an index), if the item is somehow known through collection structure.
     type ExtendMemory;
 
     /// Creates an empty collection.
@@ -18,18 +46,133 @@ pub trait SetCollection {
     /// Extends the collection, creating instance of `ExtendMemory` to later remove this element. Note that implementation should not care about item previously existing, as [`CollectionSet`] checks for `new_item` not being present in the collection prior to this call.
     fn extend(&mut self, new_item: Self::Item) -> Self::ExtendMemory;
 
+    /// Extends the collection, but only if `new_item` is not present in it already. Returns `None` in case it was.
+    ///
+    /// Default implementation simply checks for presence prior to [`SetCollection::extend`] call, but collections that are able to do both in a single lookup (like `HashSet::insert`) are encouraged to override it.
+    #[inline]
+    fn try_extend(&mut self, new_item: Self::Item) -> Option<Self::ExtendMemory> {
+        if self.contains_ref(&new_item) {
+            None
+        } else {
+            Some(self.extend(new_item))
+        }
+    }
+
+    /// Same as [`SetCollection::ExtendMemory`], but for removing several elements at once.
+    type ExtendManyMemory;
+
+    /// Extends the collection with every item, creating instance of `ExtendManyMemory` to later remove all of them. Unlike [`SetCollection::extend`], implementation must skip items that are already present in the collection (including ones repeated in `new_items`).
+    fn extend_many(
+        &mut self,
+        new_items: impl IntoIterator<Item = Self::Item>,
+    ) -> Self::ExtendManyMemory;
+
+    /// Same as [`SetCollection::extend_many`], but every item is known to be absent from the collection, and to not repeat in `new_items`.
+    ///
+    /// Default implementation simply calls [`SetCollection::extend_many`], but collections able to skip the lookups (like `Vec`) are encouraged to override it.
+    #[inline]
+    fn extend_many_unique(
+        &mut self,
+        new_items: impl IntoIterator<Item = Self::Item>,
+    ) -> Self::ExtendManyMemory {
+        self.extend_many(new_items)
+    }
+
     /// Checks if the collection contains a certain element. Only accepts in a core reference, check [`SetCollection::contains`] method if more flexibility is needed.
     fn contains_ref(&self, item: &Self::Item) -> bool;
 
+    /// Number of elements in the collection.
+    fn len(&self) -> usize;
+
+    /// Checks if the collection has no elements.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if the collection contains a certain element, looking it up by it's borrowed form. See [`StackedSet::contains_by`].
+    #[inline]
+    fn contains_by<Q: ?Sized + Query<Self>>(&self, item: &Q) -> bool {
+        self.get(item).is_some()
+    }
+
+    /// Returns the element stored in the collection, that is equal to the provided one. See [`StackedSet::get`].
+    #[inline]
+    fn get<Q: ?Sized + Query<Self>>(&self, item: &Q) -> Option<&Self::Item> {
+        item.get_in(self)
+    }
+
+    /// Returns an element of the collection satisfying the predicate, if any. See [`StackedSet::find`].
+    #[inline]
+    fn find(&self, mut predicate: impl FnMut(&Self::Item) -> bool) -> Option<&Self::Item> {
+        self.iter().find(|item| predicate(item))
+    }
+
+    /// Returns an element of the collection within the `range` and satisfying the predicate, if any. Ordered collections should override this to visit only the elements within the `range`. See [`StackedSet::find_in_range`].
+    #[inline]
+    fn find_in_range<Q, R>(
+        &self,
+        range: R,
+        mut predicate: impl FnMut(&Self::Item) -> bool,
+    ) -> Option<&Self::Item>
+    where
+        Self::Item: Borrow<Q>,
+        Q: ?Sized + Ord,
+        R: RangeBounds<Q>,
+    {
+        self.find(|item| range.contains(item.borrow()) && predicate(item))
+    }
+
     /// Checks if the collection contains a certain element.
     #[inline]
     fn contains(&self, item: impl Borrow<Self::Item>) -> bool {
         self.contains_ref(item.borrow())
     }
 
-    /// Removes an element from the collection represented by [`SetCollection::ExtendMemory`] instance.
+    /// Removes an element from the collection represented by [`SetCollection::ExtendMemory`] instance. Note that elements added later might still be in the collection, if their frames were committed (see [`CollectionSet::commit`]).
     fn remove(&mut self, present_item: Self::ExtendMemory);
 
+    /// Removes elements from the collection represented by [`SetCollection::ExtendManyMemory`] instance. Same note as for [`SetCollection::remove`] applies.
+    fn remove_many(&mut self, present_items: Self::ExtendManyMemory);
+
+    /// Returns the item represented by [`SetCollection::ExtendMemory`] instance. Used for frame introspection, see [`StackedSet::frames`].
+    fn extended_item<'a>(&'a self, memory: &'a Self::ExtendMemory) -> &'a Self::Item;
+
+    /// Type of iterator over items in order they were added, see [`SetCollection::extended_items`].
+    type Added<'i>: DoubleEndedIterator<Item = &'i Self::Item> + ExactSizeIterator + Clone
+    where
+        Self: 'i;
+
+    /// Returns items represented by [`SetCollection::ExtendManyMemory`] instance, in order they were added. Used for frame introspection, see [`StackedSet::frames`].
+    fn extended_items<'a>(&'a self, memory: &'a Self::ExtendManyMemory) -> Self::Added<'a>;
+
+    /// Returns every element of the collection in order they were added, if the collection keeps that order (like `Vec` does). Lets [`StackedSet::iter_path`] avoid walking the frames.
+    #[inline]
+    fn added(&self) -> Option<Self::Added<'_>> {
+        None
+    }
+
+    /// Maximum number of elements the collection can hold, or `None` if it can grow indefinitely. See [`CollectionSet::checked_extend`].
+    #[inline]
+    fn capacity(&self) -> Option<usize> {
+        None
+    }
+
+    /// Describes how the collection answers membership queries, see [`StackedSet::lookup`].
+    const LOOKUP: Lookup = Lookup::Linear;
+
+    /// Fast path for [`StackedSet::is_subset_of`], checking if every element of the collection is present in `other`. Returns `None`, if the collection has nothing better than the generic implementation.
+    #[inline]
+    fn is_subset_of_fast(&self, _other: &impl StackedSet<Item = Self::Item>) -> Option<bool> {
+        None
+    }
+
+    /// Fast path for [`StackedSet::is_disjoint`], checking if the collection has no elements in common with `other`. Returns `None`, if the collection has nothing better than the generic implementation.
+    #[inline]
+    fn is_disjoint_fast(&self, _other: &impl StackedSet<Item = Self::Item>) -> Option<bool> {
+        None
+    }
+
     /// Type of iterator over item references.
     type IntoIter<'i>: Iterator<Item = &'i Self::Item>
     where
@@ -39,29 +182,200 @@ pub trait SetCollection {
     fn iter(&self) -> Self::IntoIter<'_>;
 }
 
+/// Error returned by [`CollectionSet`] `checked_*` methods, if the collection is full (see [`SetCollection::capacity`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityExceeded {
+    /// Capacity of the collection
+    pub capacity: usize,
+}
+
+impl core::fmt::Display for CapacityExceeded {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "collection capacity of {} exceeded", self.capacity)
+    }
+}
+
+impl core::error::Error for CapacityExceeded {}
+
 /// [`SetCollection`]-based implementation.
 ///
 /// On my machine, worst time to check for existence is about 0.6ns/item.
-pub struct CollectionSet<'l, Collection: SetCollection>(CollectionRepr<'l, Collection>);
+pub struct CollectionSet<'l, Collection: SetCollection> {
+    collection: CollectionRef<'l, Collection>,
+    frame: Frame<'l, Collection>,
+}
 
-// In case you are wondering why is this type private - intend is to hide enum variants from public interface
-enum CollectionRepr<'l, Collection: SetCollection> {
-    Nil(Collection),
-    Fork(&'l mut Collection),
-    Extend(&'l mut Collection, Collection::ExtendMemory),
-    Moved,
+// In case you are wondering why are these types private - intend is to hide enum variants from public interface
+enum CollectionRef<'l, Collection> {
+    Owned(Collection),
+    Borrowed(&'l mut Collection),
+}
+
+/// Frame-specific part of the set. Child sets borrow collection exclusively, but their parent's frame only immutably, so frames form a cons-like list that can be walked
+struct Frame<'l, Collection: SetCollection> {
+    kind: FrameKind<Collection>,
+    // root frame is the only one with no tail
+    tail: Option<&'l Frame<'l, Collection>>,
+    // collection itself has no idea about forks, so depth is tracked here
+    depth: usize,
+    // collection length once the frame was created
+    len: usize,
+    // number of elements present before the root frame, unless something but the frames (like a commit) has changed the collection since
+    base: Option<usize>,
+}
+
+enum FrameKind<Collection: SetCollection> {
+    Fork,
+    Extend(Collection::ExtendMemory),
+    ExtendMany(Collection::ExtendManyMemory),
 }
 
 impl<Collection: SetCollection> CollectionSet<'_, Collection> {
+    /// Creates a set on top of provided collection. Useful for collections that can't be created empty with [`SetCollection::new`] in a meaningful way, like caller-provided buffers
+    ///
+    /// Elements already present in the collection are part of the set, but don't belong to any frame (see [`StackedSet::frames`])
+    #[inline]
+    pub fn new(collection: Collection) -> Self {
+        let len = collection.len();
+        Self {
+            collection: CollectionRef::Owned(collection),
+            frame: Frame {
+                kind: FrameKind::Fork,
+                tail: None,
+                depth: 0,
+                len,
+                base: Some(len),
+            },
+        }
+    }
+
     /// A private method for convenient collection mutation
     #[inline]
-    pub(self) fn c_mut(&mut self) -> &mut Collection {
-        match &mut self.0 {
-            CollectionRepr::Nil(c) => c,
-            CollectionRepr::Fork(c) | CollectionRepr::Extend(c, _) => c,
-            CollectionRepr::Moved => unreachable!("Cannot call instance method after drop"),
+    pub(crate) fn c_mut(&mut self) -> &mut Collection {
+        match &mut self.collection {
+            CollectionRef::Owned(c) => c,
+            CollectionRef::Borrowed(c) => c,
+        }
+    }
+
+    /// A private constructor for a frame on top of this one
+    #[inline]
+    pub(self) fn child(&mut self, kind: FrameKind<Collection>) -> CollectionSet<'_, Collection> {
+        let collection = match &mut self.collection {
+            CollectionRef::Owned(c) => c,
+            CollectionRef::Borrowed(c) => &mut **c,
+        };
+        let len = collection.len();
+        let added = match &kind {
+            FrameKind::Fork => 0,
+            FrameKind::Extend(_) => 1,
+            FrameKind::ExtendMany(m) => collection.extended_items(m).len(),
+        };
+        CollectionSet {
+            collection: CollectionRef::Borrowed(collection),
+            frame: Frame {
+                kind,
+                tail: Some(&self.frame),
+                depth: self.frame.depth + 1,
+                len,
+                base: self.frame.base.filter(|_| len - added == self.frame.len),
+            },
+        }
+    }
+
+    /// Drops this frame, but keeps items it has added in the collection, so that they become part of the parent set. This allows using the set as an "out parameter" (see the [crate-level documentation](crate))
+    ///
+    /// Committed items don't belong to any frame anymore, so they are not reported by [`StackedSet::frames`] and related methods, and are only removed along with the collection itself. Committing the empty set does nothing
+    ///
+    /// There's no such method for [`ConsSet`](crate::StackCons), as its items are stored in the frames themselves
+    #[inline]
+    pub fn commit(mut self) {
+        self.frame.kind = FrameKind::Fork;
+    }
+
+    /// Checks that the collection can hold `additional` more elements
+    #[inline]
+    fn check_capacity(&self, additional: usize) -> Result<(), CapacityExceeded> {
+        match Collection::capacity(self) {
+            Some(capacity) if Collection::len(self) + additional > capacity => {
+                Err(CapacityExceeded { capacity })
+            }
+            _ => Ok(()),
         }
     }
+
+    /// Same as [`StackedSet::try_extend`], but fails instead of extending a full collection (see [`SetCollection::capacity`])
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CapacityExceeded`] if the item is not present, and the collection is full
+    #[inline]
+    pub fn checked_try_extend(
+        &mut self,
+        new_item: Collection::Item,
+    ) -> Result<Extended<CollectionSet<'_, Collection>>, CapacityExceeded> {
+        if let Err(error) = self.check_capacity(1) {
+            // full collection can only be "extended" with an item it already has
+            if !Collection::contains_ref(self, &new_item) {
+                return Err(error);
+            }
+            return Ok(Extended::AlreadyPresent(self.child(FrameKind::Fork)));
+        }
+        Ok(self.try_extend(new_item))
+    }
+
+    /// Same as [`StackedSet::extend`], but fails instead of extending a full collection
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CapacityExceeded`] under the same conditions as [`CollectionSet::checked_try_extend`]
+    #[inline]
+    pub fn checked_extend(
+        &mut self,
+        new_item: Collection::Item,
+    ) -> Result<CollectionSet<'_, Collection>, CapacityExceeded> {
+        self.checked_try_extend(new_item).map(Extended::into_inner)
+    }
+
+    /// Same as [`StackedSet::extend_many`], but fails instead of overflowing the collection
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CapacityExceeded`] if the collection can't hold all the new items. Collection is left intact in that case
+    #[inline]
+    pub fn checked_extend_many<I>(
+        &mut self,
+        new_items: I,
+    ) -> Result<CollectionSet<'_, Collection>, CapacityExceeded>
+    where
+        I: IntoIterator<Item = Collection::Item, IntoIter: Clone>,
+        Collection::Item: PartialEq,
+    {
+        // items are counted on a copy of the iterator, before the collection is touched
+        let new_items = new_items.into_iter();
+        let added = new_items
+            .clone()
+            .enumerate()
+            .filter(|(index, item)| {
+                !new_items
+                    .clone()
+                    .take(*index)
+                    .any(|preceding| preceding == *item)
+                    && !Collection::contains_ref(self, item)
+            })
+            .count();
+        self.check_capacity(added)?;
+        Ok(self.extend_many(new_items))
+    }
+
+    /// Creates a fork of this set, that allows adding and removing items in arbitrary order, rather than lexically scoped frames. See [`Journal`]
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn journal(&mut self) -> Journal<'_, Collection> {
+        Journal::new(self.child(FrameKind::Fork))
+    }
 }
 
 impl<Collection: SetCollection> Deref for CollectionSet<'_, Collection> {
@@ -69,10 +383,9 @@ impl<Collection: SetCollection> Deref for CollectionSet<'_, Collection> {
 
     #[inline]
     fn deref(&self) -> &Self::Target {
-        match &self.0 {
-            CollectionRepr::Nil(c) => c,
-            CollectionRepr::Fork(c) | CollectionRepr::Extend(c, _) => c,
-            CollectionRepr::Moved => unreachable!("Cannot call instance method after drop"),
+        match &self.collection {
+            CollectionRef::Owned(c) => c,
+            CollectionRef::Borrowed(c) => c,
         }
     }
 }
@@ -101,19 +414,31 @@ impl<Collection: SetCollection + Debug> Debug for CollectionSet<'_, Collection>
 impl<Collection: SetCollection> Drop for CollectionSet<'_, Collection> {
     #[inline]
     fn drop(&mut self) {
-        let repr = core::mem::replace(&mut self.0, CollectionRepr::Moved);
-        if let CollectionRepr::Extend(c, m) = repr {
-            c.remove(m);
+        match core::mem::replace(&mut self.frame.kind, FrameKind::Fork) {
+            FrameKind::Fork => {}
+            FrameKind::Extend(m) => self.c_mut().remove(m),
+            FrameKind::ExtendMany(m) => self.c_mut().remove_many(m),
         }
     }
 }
 
+impl<Collection: SetCollection + ?Sized> Backend for Collection {
+    type Item = Collection::Item;
+
+    type View<'a>
+        = &'a Collection
+    where
+        Self: 'a;
+}
+
 impl<Collection: SetCollection> StackedSet for CollectionSet<'_, Collection> {
     type Item = Collection::Item;
 
+    type Backend = Collection;
+
     #[inline]
     fn empty() -> Self {
-        Self(CollectionRepr::Nil(Collection::new()))
+        Self::new(Collection::new())
     }
 
     #[inline]
@@ -121,24 +446,80 @@ impl<Collection: SetCollection> StackedSet for CollectionSet<'_, Collection> {
         Collection::contains_ref(self, item)
     }
 
+    #[inline]
+    fn contains_by<Q: ?Sized + Query<Self::Backend>>(&self, item: &Q) -> bool {
+        Collection::contains_by(self, item)
+    }
+
+    #[inline]
+    fn get<Q: ?Sized + Query<Self::Backend>>(&self, item: &Q) -> Option<&Self::Item> {
+        Collection::get(self, item)
+    }
+
+    #[inline]
+    fn find(&self, predicate: impl FnMut(&Self::Item) -> bool) -> Option<&Self::Item> {
+        Collection::find(self, predicate)
+    }
+
+    #[inline]
+    fn find_in_range<Q, R>(
+        &self,
+        range: R,
+        predicate: impl FnMut(&Self::Item) -> bool,
+    ) -> Option<&Self::Item>
+    where
+        Self::Item: Borrow<Q>,
+        Q: ?Sized + Ord,
+        R: RangeBounds<Q>,
+    {
+        Collection::find_in_range(self, range, predicate)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        Collection::len(self)
+    }
+
+    #[inline]
+    fn depth(&self) -> usize {
+        self.frame.depth
+    }
+
     type Shorten<'new>
         = CollectionSet<'new, Collection>
     where
         Self: 'new;
 
     #[inline]
-    fn extend(&mut self, new_item: Self::Item) -> Self::Shorten<'_> {
-        if self.contains_ref(&new_item) {
-            CollectionSet(CollectionRepr::Fork(self.c_mut()))
-        } else {
-            let m = self.c_mut().extend(new_item);
-            CollectionSet(CollectionRepr::Extend(self.c_mut(), m))
+    fn try_extend(&mut self, new_item: Self::Item) -> Extended<Self::Shorten<'_>> {
+        match self.c_mut().try_extend(new_item) {
+            Some(m) => Extended::Inserted(self.child(FrameKind::Extend(m))),
+            None => Extended::AlreadyPresent(self.child(FrameKind::Fork)),
         }
     }
 
+    #[inline]
+    fn extend_unique(&mut self, new_item: Self::Item) -> Self::Shorten<'_> {
+        debug_assert!(
+            !Collection::contains_ref(self, &new_item),
+            "Item passed to `extend_unique` is already present in the set"
+        );
+        let m = self.c_mut().extend(new_item);
+        self.child(FrameKind::Extend(m))
+    }
+
+    #[inline]
+    fn extend_many(
+        &mut self,
+        new_items: impl IntoIterator<Item = Self::Item>,
+    ) -> Self::Shorten<'_> {
+        let m = self.c_mut().extend_many(new_items);
+        self.child(FrameKind::ExtendMany(m))
+    }
+
     #[inline]
     fn fork(&mut self) -> Self::Shorten<'_> {
-        CollectionSet(CollectionRepr::Fork(self.c_mut()))
+        self.child(FrameKind::Fork)
     }
 
     type IntoIter<'i>
@@ -151,4 +532,171 @@ impl<Collection: SetCollection> StackedSet for CollectionSet<'_, Collection> {
         let c: &Collection = self;
         c.iter()
     }
+
+    #[inline]
+    fn lookup(&self) -> Lookup {
+        Collection::LOOKUP
+    }
+
+    #[inline]
+    fn is_subset_of(&self, other: &impl StackedSet<Item = Self::Item>) -> bool {
+        Collection::is_subset_of_fast(self, other)
+            .unwrap_or_else(|| algebra::is_subset(self, other))
+    }
+
+    #[inline]
+    fn is_disjoint(&self, other: &impl StackedSet<Item = Self::Item>) -> bool {
+        Collection::is_disjoint_fast(self, other)
+            .unwrap_or_else(|| algebra::is_disjoint(self, other))
+    }
+
+    type Frames<'i>
+        = CollectionFrames<'i, Collection>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn frames(&self) -> Self::Frames<'_> {
+        CollectionFrames {
+            collection: self,
+            frame: &self.frame,
+            items: None,
+        }
+    }
+
+    type Path<'i>
+        = CollectionPath<'i, Collection>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn iter_path(&self) -> Self::Path<'_> {
+        let collection: &Collection = self;
+        // frames' items are the latest ones in the collection, unless the collection was changed by something else
+        let added = (self.frame.len == collection.len())
+            .then_some(self.frame.base)
+            .flatten()
+            .and_then(|base| Some((base, collection.added()?)));
+        CollectionPath(match added {
+            Some((base, mut added)) => {
+                if let Some(before) = base.checked_sub(1) {
+                    let _ = added.nth(before);
+                }
+                PathRepr::Added(added)
+            }
+            #[cfg(feature = "alloc")]
+            None => {
+                let mut items: alloc::vec::Vec<_> = self.iter_path_rev().collect();
+                items.reverse();
+                PathRepr::Collected(items.into_iter())
+            }
+            #[cfg(not(feature = "alloc"))]
+            None => PathRepr::Frames(crate::PathIter::new(self)),
+        })
+    }
+}
+
+/// Iterator over [`CollectionSet`] frames, see [`StackedSet::frames`].
+#[allow(missing_debug_implementations)]
+pub struct CollectionFrames<'i, Collection: SetCollection> {
+    collection: &'i Collection,
+    frame: &'i Frame<'i, Collection>,
+    // items of `FrameKind::ExtendMany` frame yet to be reported, latest first
+    items: Option<Collection::Added<'i>>,
+}
+
+impl<'i, Collection: SetCollection> Iterator for CollectionFrames<'i, Collection> {
+    type Item = (usize, Option<&'i Collection::Item>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let frame = self.frame;
+        // root frame is not reported
+        let tail = frame.tail?;
+        let item = match &frame.kind {
+            FrameKind::Fork => None,
+            FrameKind::Extend(m) => Some(self.collection.extended_item(m)),
+            FrameKind::ExtendMany(m) => {
+                let items = self
+                    .items
+                    .get_or_insert_with(|| self.collection.extended_items(m));
+                // frame that added nothing is reported once, as a fork
+                if items.len() > 1 {
+                    return Some((frame.depth, items.next_back()));
+                }
+                items.next_back()
+            }
+        };
+        self.frame = tail;
+        self.items = None;
+        Some((frame.depth, item))
+    }
+}
+
+/// Iterator over [`CollectionSet`] items in order they were added, see [`StackedSet::iter_path`].
+///
+/// Collections keeping that order (see [`SetCollection::added`]) are iterated directly, taking linear time. Others, as well as frames created after a [`commit`](CollectionSet::commit) (that breaks the order), have their frames collected into a `Vec` first - still linear, but allocating. Without `alloc`, frames are walked once per item instead, taking quadratic time
+#[allow(missing_debug_implementations)]
+pub struct CollectionPath<'i, Collection: SetCollection>(PathRepr<'i, Collection>);
+
+// bound is only inferred from `Frames` variant, i.e. without `alloc`
+#[allow(explicit_outlives_requirements)]
+enum PathRepr<'i, Collection: SetCollection + 'i> {
+    Added(Collection::Added<'i>),
+    #[cfg(feature = "alloc")]
+    Collected(alloc::vec::IntoIter<&'i Collection::Item>),
+    #[cfg(not(feature = "alloc"))]
+    Frames(crate::PathIter<'i, CollectionSet<'i, Collection>>),
+}
+
+impl<'i, Collection: SetCollection + 'i> Clone for CollectionPath<'i, Collection> {
+    #[inline]
+    fn clone(&self) -> Self {
+        CollectionPath(match &self.0 {
+            PathRepr::Added(items) => PathRepr::Added(items.clone()),
+            #[cfg(feature = "alloc")]
+            PathRepr::Collected(items) => PathRepr::Collected(items.clone()),
+            #[cfg(not(feature = "alloc"))]
+            PathRepr::Frames(items) => PathRepr::Frames(items.clone()),
+        })
+    }
 }
+
+impl<'i, Collection: SetCollection + 'i> Iterator for CollectionPath<'i, Collection> {
+    type Item = &'i Collection::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            PathRepr::Added(items) => items.next(),
+            #[cfg(feature = "alloc")]
+            PathRepr::Collected(items) => items.next(),
+            #[cfg(not(feature = "alloc"))]
+            PathRepr::Frames(items) => items.next(),
+        }
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match &mut self.0 {
+            PathRepr::Added(items) => items.nth(n),
+            #[cfg(feature = "alloc")]
+            PathRepr::Collected(items) => items.nth(n),
+            #[cfg(not(feature = "alloc"))]
+            PathRepr::Frames(items) => items.nth(n),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.0 {
+            PathRepr::Added(items) => items.size_hint(),
+            #[cfg(feature = "alloc")]
+            PathRepr::Collected(items) => items.size_hint(),
+            #[cfg(not(feature = "alloc"))]
+            PathRepr::Frames(items) => items.size_hint(),
+        }
+    }
+}
+
+impl<'i, Collection: SetCollection + 'i> ExactSizeIterator for CollectionPath<'i, Collection> {}
@@ -0,0 +1,171 @@
+use core::{borrow::Borrow, fmt::Debug, ops::Deref};
+
+use crate::StackedBag;
+
+/// Kind of interface a "counting collection" should expose for [`StackedBag`] implementation to be built on top of it. This is a multiset counterpart of [`SetCollection`](crate::collection::SetCollection).
+///
+/// To get a [`StackedBag`] implementor, just wrap your collection into [`CollectionBag`]. Original collection will still be available via `Deref`/`Borrow`/`AsRef`.
+pub trait BagCollection {
+    /// Element type stored in the collection.
+    type Item;
+
+    /// A some sort of memory that can be used to decrement item's multiplicity back.
+    type ExtendMemory;
+
+    /// Creates an empty collection.
+    fn new() -> Self;
+
+    /// Increments multiplicity of the item, creating instance of `ExtendMemory` to later decrement it back. Note that item might be present in the collection already.
+    fn extend(&mut self, new_item: Self::Item) -> Self::ExtendMemory;
+
+    /// Returns multiplicity of the item (zero, if it's not present).
+    fn count_ref(&self, item: &Self::Item) -> usize;
+
+    /// Decrements multiplicity of the item represented by [`BagCollection::ExtendMemory`] instance, dropping it completely once it reaches zero.
+    fn remove(&mut self, present_item: Self::ExtendMemory);
+
+    /// Type of iterator over distinct items and their multiplicities.
+    type IntoIter<'i>: Iterator<Item = (&'i Self::Item, usize)>
+    where
+        Self: 'i;
+
+    /// Creates iterator over distinct items and their multiplicities.
+    fn iter(&self) -> Self::IntoIter<'_>;
+}
+
+/// [`BagCollection`]-based implementation.
+pub struct CollectionBag<'l, Collection: BagCollection> {
+    repr: CollectionBagRepr<'l, Collection>,
+    // collection stores items with multiplicities, so total count is easier to track here
+    len: usize,
+    // collection itself has no idea about forks, so depth is tracked here
+    depth: usize,
+}
+
+// In case you are wondering why is this type private - intend is to hide enum variants from public interface
+enum CollectionBagRepr<'l, Collection: BagCollection> {
+    Nil(Collection),
+    Fork(&'l mut Collection),
+    Extend(&'l mut Collection, Collection::ExtendMemory),
+    Moved,
+}
+
+impl<Collection: BagCollection> CollectionBag<'_, Collection> {
+    /// A private method for convenient collection mutation
+    #[inline]
+    pub(self) fn c_mut(&mut self) -> &mut Collection {
+        match &mut self.repr {
+            CollectionBagRepr::Nil(c) => c,
+            CollectionBagRepr::Fork(c) | CollectionBagRepr::Extend(c, _) => c,
+            CollectionBagRepr::Moved => unreachable!("Cannot call instance method after drop"),
+        }
+    }
+}
+
+impl<Collection: BagCollection> Deref for CollectionBag<'_, Collection> {
+    type Target = Collection;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        match &self.repr {
+            CollectionBagRepr::Nil(c) => c,
+            CollectionBagRepr::Fork(c) | CollectionBagRepr::Extend(c, _) => c,
+            CollectionBagRepr::Moved => unreachable!("Cannot call instance method after drop"),
+        }
+    }
+}
+
+impl<Collection: BagCollection> Borrow<Collection> for CollectionBag<'_, Collection> {
+    #[inline]
+    fn borrow(&self) -> &Collection {
+        self
+    }
+}
+
+impl<Collection: BagCollection> AsRef<Collection> for CollectionBag<'_, Collection> {
+    #[inline]
+    fn as_ref(&self) -> &Collection {
+        self
+    }
+}
+
+impl<Collection: BagCollection + Debug> Debug for CollectionBag<'_, Collection> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        <Collection as core::fmt::Debug>::fmt(&**self, f)
+    }
+}
+
+impl<Collection: BagCollection> Drop for CollectionBag<'_, Collection> {
+    #[inline]
+    fn drop(&mut self) {
+        let repr = core::mem::replace(&mut self.repr, CollectionBagRepr::Moved);
+        if let CollectionBagRepr::Extend(c, m) = repr {
+            c.remove(m);
+        }
+    }
+}
+
+impl<Collection: BagCollection> StackedBag for CollectionBag<'_, Collection> {
+    type Item = Collection::Item;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            repr: CollectionBagRepr::Nil(Collection::new()),
+            len: 0,
+            depth: 0,
+        }
+    }
+
+    #[inline]
+    fn count_ref(&self, item: &Self::Item) -> usize {
+        Collection::count_ref(self, item)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn depth(&self) -> usize {
+        self.depth
+    }
+
+    type Shorten<'new>
+        = CollectionBag<'new, Collection>
+    where
+        Self: 'new;
+
+    #[inline]
+    fn extend(&mut self, new_item: Self::Item) -> Self::Shorten<'_> {
+        let (len, depth) = (self.len + 1, self.depth + 1);
+        let m = self.c_mut().extend(new_item);
+        CollectionBag {
+            repr: CollectionBagRepr::Extend(self.c_mut(), m),
+            len,
+            depth,
+        }
+    }
+
+    #[inline]
+    fn fork(&mut self) -> Self::Shorten<'_> {
+        CollectionBag {
+            len: self.len,
+            depth: self.depth + 1,
+            repr: CollectionBagRepr::Fork(self.c_mut()),
+        }
+    }
+
+    type IntoIter<'i>
+        = Collection::IntoIter<'i>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn iter(&self) -> Self::IntoIter<'_> {
+        let c: &Collection = self;
+        c.iter()
+    }
+}
@@ -0,0 +1,180 @@
+use core::{borrow::Borrow, fmt::Debug, ops::Deref};
+
+use crate::StackedMap;
+
+/// Kind of interface a "map collection" should expose for [`StackedMap`] implementation to be built on top of it. This is a map counterpart of [`SetCollection`](crate::collection::SetCollection).
+///
+/// To get a [`StackedMap`] implementor, just wrap your collection into [`CollectionMap`]. Original collection will still be available via `Deref`/`Borrow`/`AsRef`.
+pub trait MapCollection {
+    /// Key type stored in the collection.
+    type Key;
+
+    /// Value type stored in the collection.
+    type Value;
+
+    /// A some sort of memory that can be used to undo the insertion. Since insertions may shadow previous bindings, this usually has to contain the previous value.
+    type InsertMemory;
+
+    /// Creates an empty collection.
+    fn new() -> Self;
+
+    /// Binds the value to the key, creating instance of `InsertMemory` to later undo this insertion. Note that key might be bound already, in which case previous binding must be restored on [`MapCollection::remove`].
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Self::InsertMemory;
+
+    /// Returns value bound to the key, if any.
+    fn get_ref(&self, key: &Self::Key) -> Option<&Self::Value>;
+
+    /// Number of keys in the collection.
+    fn len(&self) -> usize;
+
+    /// Checks if the collection has no keys.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Undoes an insertion represented by [`MapCollection::InsertMemory`] instance, restoring the shadowed binding (if there was any).
+    fn remove(&mut self, memory: Self::InsertMemory);
+
+    /// Type of iterator over bindings.
+    type IntoIter<'i>: Iterator<Item = (&'i Self::Key, &'i Self::Value)>
+    where
+        Self: 'i;
+
+    /// Creates iterator over bindings.
+    fn iter(&self) -> Self::IntoIter<'_>;
+}
+
+/// [`MapCollection`]-based implementation.
+pub struct CollectionMap<'l, Collection: MapCollection> {
+    repr: CollectionMapRepr<'l, Collection>,
+    // collection itself has no idea about forks, so depth is tracked here
+    depth: usize,
+}
+
+// In case you are wondering why is this type private - intend is to hide enum variants from public interface
+enum CollectionMapRepr<'l, Collection: MapCollection> {
+    Nil(Collection),
+    Fork(&'l mut Collection),
+    Insert(&'l mut Collection, Collection::InsertMemory),
+    Moved,
+}
+
+impl<Collection: MapCollection> CollectionMap<'_, Collection> {
+    /// A private method for convenient collection mutation
+    #[inline]
+    pub(self) fn c_mut(&mut self) -> &mut Collection {
+        match &mut self.repr {
+            CollectionMapRepr::Nil(c) => c,
+            CollectionMapRepr::Fork(c) | CollectionMapRepr::Insert(c, _) => c,
+            CollectionMapRepr::Moved => unreachable!("Cannot call instance method after drop"),
+        }
+    }
+}
+
+impl<Collection: MapCollection> Deref for CollectionMap<'_, Collection> {
+    type Target = Collection;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        match &self.repr {
+            CollectionMapRepr::Nil(c) => c,
+            CollectionMapRepr::Fork(c) | CollectionMapRepr::Insert(c, _) => c,
+            CollectionMapRepr::Moved => unreachable!("Cannot call instance method after drop"),
+        }
+    }
+}
+
+impl<Collection: MapCollection> Borrow<Collection> for CollectionMap<'_, Collection> {
+    #[inline]
+    fn borrow(&self) -> &Collection {
+        self
+    }
+}
+
+impl<Collection: MapCollection> AsRef<Collection> for CollectionMap<'_, Collection> {
+    #[inline]
+    fn as_ref(&self) -> &Collection {
+        self
+    }
+}
+
+impl<Collection: MapCollection + Debug> Debug for CollectionMap<'_, Collection> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        <Collection as core::fmt::Debug>::fmt(&**self, f)
+    }
+}
+
+impl<Collection: MapCollection> Drop for CollectionMap<'_, Collection> {
+    #[inline]
+    fn drop(&mut self) {
+        let repr = core::mem::replace(&mut self.repr, CollectionMapRepr::Moved);
+        if let CollectionMapRepr::Insert(c, m) = repr {
+            c.remove(m);
+        }
+    }
+}
+
+impl<Collection: MapCollection> StackedMap for CollectionMap<'_, Collection> {
+    type Key = Collection::Key;
+
+    type Value = Collection::Value;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            repr: CollectionMapRepr::Nil(Collection::new()),
+            depth: 0,
+        }
+    }
+
+    #[inline]
+    fn get_ref(&self, key: &Self::Key) -> Option<&Self::Value> {
+        Collection::get_ref(self, key)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        Collection::len(self)
+    }
+
+    #[inline]
+    fn depth(&self) -> usize {
+        self.depth
+    }
+
+    type Shorten<'new>
+        = CollectionMap<'new, Collection>
+    where
+        Self: 'new;
+
+    #[inline]
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Self::Shorten<'_> {
+        let depth = self.depth + 1;
+        let m = self.c_mut().insert(key, value);
+        CollectionMap {
+            repr: CollectionMapRepr::Insert(self.c_mut(), m),
+            depth,
+        }
+    }
+
+    #[inline]
+    fn fork(&mut self) -> Self::Shorten<'_> {
+        CollectionMap {
+            depth: self.depth + 1,
+            repr: CollectionMapRepr::Fork(self.c_mut()),
+        }
+    }
+
+    type IntoIter<'i>
+        = Collection::IntoIter<'i>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn iter(&self) -> Self::IntoIter<'_> {
+        let c: &Collection = self;
+        c.iter()
+    }
+}
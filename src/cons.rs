@@ -1,22 +1,229 @@
-use core::{borrow::Borrow, fmt::Debug};
+use core::{borrow::Borrow, fmt::Debug, marker::PhantomData, ops::Deref};
 
-use crate::StackedSet;
+use crate::{Backend, Extended, Fingerprint, NoFingerprint, Query, StackedSet};
 
 /// `Cons list`-like implementation of [`StackedSet`]
 ///
 /// On my machine, worst time to check for existence is about 2ns/item
-pub struct ConsSet<'tail, Item>(ConsRepr<'tail, Item>);
+///
+/// Each node carries a [`Fingerprint`] of all the items below it, letting lookups of absent items stop early. Default [`NoFingerprint`] does nothing, pick [`Bloom64`](crate::Bloom64) or [`Bloom128`](crate::Bloom128) for [`Hash`](core::hash::Hash) items
+pub struct ConsSet<'tail, Item, F = NoFingerprint>(ConsRepr<'tail, Item, F>);
 
 // In case you are wondering why is this type private - intend is to hide enum variants from public interface
-enum ConsRepr<'tail, Item> {
+enum ConsRepr<'tail, Item, F> {
     Nil,
     Con {
         this: Option<Item>,
-        tail: &'tail ConsSet<'tail, Item>,
+        link: Link<'tail, Item, F>,
+    },
+    Many {
+        these: ManyItems<'tail, Item>,
+        // which of the first `u64::BITS` items of the slice are actually new, found once the node is created
+        new: u64,
+        link: Link<'tail, Item, F>,
     },
 }
 
-impl<Item: PartialEq + Debug> Debug for ConsSet<'_, Item> {
+/// Items of a `ConsRepr::Many` node
+enum ManyItems<'tail, Item> {
+    // slice passed to inherent `ConsSet::extend_many`
+    Borrowed(&'tail [Item]),
+    // items passed to `StackedSet::extend_many`, which the node has to keep itself
+    #[cfg(feature = "alloc")]
+    Owned(alloc::boxed::Box<[Item]>),
+}
+
+impl<Item> Deref for ManyItems<'_, Item> {
+    type Target = [Item];
+
+    #[inline]
+    fn deref(&self) -> &[Item] {
+        match self {
+            ManyItems::Borrowed(these) => these,
+            #[cfg(feature = "alloc")]
+            ManyItems::Owned(these) => these,
+        }
+    }
+}
+
+/// Part of every non-empty node, pointing to the nodes below it
+struct Link<'tail, Item, F> {
+    // the frame this one was produced from
+    tail: &'tail ConsSet<'tail, Item, F>,
+    // nearest node below holding any items, so that lookups skip forks and duplicate extends
+    below: &'tail ConsSet<'tail, Item, F>,
+    // farther ancestor, so that `nth_ancestor` takes logarithmic number of steps (see Myers' "An applicative random-access stack")
+    jump: &'tail ConsSet<'tail, Item, F>,
+    // both counts are cached here, so that they could be answered without walking the list
+    len: usize,
+    depth: usize,
+    // summary of this node and the ones below it
+    fingerprint: F,
+}
+
+impl<'tail, Item, F: Fingerprint<Item>> ConsSet<'tail, Item, F> {
+    /// A private constructor for a node on top of `tail`
+    #[inline]
+    fn con(this: Option<Item>, tail: &'tail Self) -> Self {
+        let fingerprint = match &this {
+            Some(this) => tail.fingerprint().union(F::of(this)),
+            None => tail.fingerprint(),
+        };
+        let len = tail.len_cached() + usize::from(this.is_some());
+        ConsSet(ConsRepr::Con {
+            this,
+            link: Link::new(tail, len, fingerprint),
+        })
+    }
+
+    /// Cached fingerprint
+    #[inline]
+    fn fingerprint(&self) -> F {
+        self.link().map_or(F::EMPTY, |link| link.fingerprint)
+    }
+}
+
+impl<'tail, Item, F> Link<'tail, Item, F> {
+    /// Links a node with `len` items on top of `tail`
+    #[inline]
+    fn new(tail: &'tail ConsSet<'tail, Item, F>, len: usize, fingerprint: F) -> Self {
+        let depth = tail.depth_cached();
+        // jump over twice the tail's jump, once the two are of equal length
+        let tail_jump = tail.jump();
+        let jump = if depth - tail_jump.depth_cached()
+            == tail_jump.depth_cached() - tail_jump.jump().depth_cached()
+        {
+            tail_jump.jump()
+        } else {
+            tail
+        };
+        Link {
+            tail,
+            below: tail.holding(),
+            jump,
+            len,
+            depth: depth + 1,
+            fingerprint,
+        }
+    }
+}
+
+impl<'tail, Item, F> ConsSet<'tail, Item, F> {
+    /// Returns the set this one was produced from, i.e. the frame below this one. Empty set has no parent
+    #[inline]
+    #[must_use]
+    pub fn parent(&self) -> Option<&ConsSet<'tail, Item, F>> {
+        self.link().map(|link| link.tail)
+    }
+
+    /// Returns the set `n` frames below this one, i.e. `n`-th [`parent`](Self::parent), taking logarithmic number of steps. Zeroth ancestor is the set itself, and ancestors past the empty set don't exist
+    #[inline]
+    #[must_use]
+    pub fn nth_ancestor(&self, n: usize) -> Option<&ConsSet<'tail, Item, F>> {
+        let target = self.depth_cached().checked_sub(n)?;
+        let mut node = self;
+        while let Some(link) = node.link().filter(|link| link.depth > target) {
+            node = if link.jump.depth_cached() >= target {
+                link.jump
+            } else {
+                link.tail
+            };
+        }
+        Some(node)
+    }
+
+    /// Links to the nodes below, unless the set is empty
+    #[inline]
+    fn link(&self) -> Option<&Link<'tail, Item, F>> {
+        match &self.0 {
+            ConsRepr::Nil => None,
+            ConsRepr::Con { link, .. } | ConsRepr::Many { link, .. } => Some(link),
+        }
+    }
+
+    /// Cached length
+    #[inline]
+    fn len_cached(&self) -> usize {
+        self.link().map_or(0, |link| link.len)
+    }
+
+    /// Cached depth
+    #[inline]
+    fn depth_cached(&self) -> usize {
+        self.link().map_or(0, |link| link.depth)
+    }
+
+    /// Jump link, or the set itself if it's empty
+    #[inline]
+    fn jump(&self) -> &ConsSet<'tail, Item, F> {
+        self.link().map_or(self, |link| link.jump)
+    }
+
+    /// Nearest node holding any items, starting with this one. Empty set is the last one in the chain
+    #[inline]
+    fn holding(&self) -> &ConsSet<'tail, Item, F> {
+        match &self.0 {
+            ConsRepr::Con { this: None, link } => link.below,
+            ConsRepr::Many { link, .. } if link.len == link.tail.len_cached() => link.below,
+            _ => self,
+        }
+    }
+}
+
+impl<Item: PartialEq, F: Fingerprint<Item>> ConsSet<'_, Item, F> {
+    /// Same as [`StackedSet::extend_many`], but the items are only borrowed by the new frame, so that it stays alloc-free
+    #[inline]
+    #[must_use = "Provided values are only contained in a set returned form this call. Despite requiring exclusive borrow, original set is should not be expected to change. Check documentation for more details."]
+    pub fn extend_many<'a>(&'a mut self, new_items: &'a [Item]) -> ConsSet<'a, Item, F> {
+        ConsSet::many(ManyItems::Borrowed(new_items), self)
+    }
+
+    /// A private constructor for a node holding several items on top of `tail`
+    #[inline]
+    fn many<'a>(new_items: ManyItems<'a, Item>, tail: &'a Self) -> ConsSet<'a, Item, F> {
+        let (mut added, mut new) = (0, 0);
+        for index in 0..new_items.len() {
+            if ConsRepr::looks_new_in_many(&new_items, tail, index) {
+                added += 1;
+                if index < 64 {
+                    new |= 1 << index;
+                }
+            }
+        }
+        let fingerprint = new_items
+            .iter()
+            .fold(tail.fingerprint(), |fingerprint, item| {
+                fingerprint.union(F::of(item))
+            });
+        let len = tail.len_cached() + added;
+        ConsSet(ConsRepr::Many {
+            these: new_items,
+            new,
+            link: Link::new(tail, len, fingerprint),
+        })
+    }
+}
+
+impl<Item: PartialEq, F: Fingerprint<Item>> ConsRepr<'_, Item, F> {
+    /// Checks if `these[index]` is actually a new item in the set, i.e. it's not present further in the list, nor before it in the slice
+    #[inline]
+    fn looks_new_in_many(these: &[Item], tail: &ConsSet<'_, Item, F>, index: usize) -> bool {
+        let item = &these[index];
+        !these[..index].contains(item) && !tail.contains_ref(item)
+    }
+
+    /// Same as [`ConsRepr::looks_new_in_many`], but answered by `new` mask of the node. Only items past the mask are looked up, unless the node added either all of them or none
+    #[inline]
+    fn is_new_in_many(these: &[Item], new: u64, link: &Link<'_, Item, F>, index: usize) -> bool {
+        if index < 64 {
+            return new & (1 << index) != 0;
+        }
+        let added = link.len - link.tail.len_cached();
+        added == these.len() || added != 0 && ConsRepr::looks_new_in_many(these, link.tail, index)
+    }
+}
+
+impl<Item: PartialEq + Debug, F: Fingerprint<Item>> Debug for ConsSet<'_, Item, F> {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let mut list = f.debug_list();
@@ -27,9 +234,11 @@ impl<Item: PartialEq + Debug> Debug for ConsSet<'_, Item> {
     }
 }
 
-impl<Item: PartialEq> StackedSet for ConsSet<'_, Item> {
+impl<Item: PartialEq, F: Fingerprint<Item>> StackedSet for ConsSet<'_, Item, F> {
     type Item = Item;
 
+    type Backend = ConsBackend<Item, F>;
+
     #[inline]
     fn empty() -> Self {
         ConsSet(ConsRepr::Nil)
@@ -37,79 +246,403 @@ impl<Item: PartialEq> StackedSet for ConsSet<'_, Item> {
 
     #[inline]
     fn contains_ref(&self, item: &Self::Item) -> bool {
-        match &self.0 {
-            ConsRepr::Nil => false,
-            ConsRepr::Con { this, tail } => {
-                if this.as_ref().is_some_and(|this| this == item.borrow()) {
-                    true
-                } else {
-                    tail.contains(item)
+        let fingerprint = F::of(item);
+        let mut node = self.holding();
+        loop {
+            // fingerprint of a node summarizes the ones below it as well, so the walk stops as soon as it rules the item out
+            if !node.fingerprint().may_contain(fingerprint) {
+                break false;
+            }
+            match &node.0 {
+                ConsRepr::Nil => break false,
+                ConsRepr::Con { this, link } => {
+                    if this.as_ref().is_some_and(|this| this == item) {
+                        break true;
+                    }
+                    node = link.below;
+                }
+                ConsRepr::Many { these, link, .. } => {
+                    if these.contains(item) {
+                        break true;
+                    }
+                    node = link.below;
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn get<Q: ?Sized + Query<Self::Backend>>(&self, item: &Q) -> Option<&Self::Item> {
+        item.get_in(self)
+    }
+
+    #[inline]
+    fn find(&self, mut predicate: impl FnMut(&Self::Item) -> bool) -> Option<&Self::Item> {
+        // no need to skip items repeated in `ConsRepr::Many` slices, as any of them could be reported
+        let mut node = self.holding();
+        loop {
+            match &node.0 {
+                ConsRepr::Nil => break None,
+                ConsRepr::Con { this, link } => {
+                    if let Some(this) = this.as_ref().filter(|this| predicate(this)) {
+                        break Some(this);
+                    }
+                    node = link.below;
+                }
+                ConsRepr::Many { these, link, .. } => {
+                    if let Some(this) = these.iter().find(|this| predicate(this)) {
+                        break Some(this);
+                    }
+                    node = link.below;
                 }
             }
         }
     }
 
+    #[inline]
+    fn len(&self) -> usize {
+        self.len_cached()
+    }
+
+    #[inline]
+    fn depth(&self) -> usize {
+        self.depth_cached()
+    }
+
     type Shorten<'new>
-        = ConsSet<'new, Item>
+        = ConsSet<'new, Item, F>
     where
         Self: 'new;
 
     #[inline]
-    fn extend(&mut self, new_item: Item) -> Self::Shorten<'_> {
+    fn try_extend(&mut self, new_item: Item) -> Extended<Self::Shorten<'_>> {
         if self.contains_ref(&new_item) {
-            ConsSet(ConsRepr::Con {
-                this: None,
-                tail: self,
-            })
+            Extended::AlreadyPresent(ConsSet::con(None, self))
         } else {
-            ConsSet(ConsRepr::Con {
-                this: Some(new_item),
-                tail: self,
-            })
+            Extended::Inserted(ConsSet::con(Some(new_item), self))
+        }
+    }
+
+    #[inline]
+    fn extend_unique(&mut self, new_item: Item) -> Self::Shorten<'_> {
+        debug_assert!(
+            !self.contains_ref(&new_item),
+            "Item passed to `extend_unique` is already present in the set"
+        );
+        ConsSet::con(Some(new_item), self)
+    }
+
+    #[inline]
+    fn extend_many(&mut self, new_items: impl IntoIterator<Item = Item>) -> Self::Shorten<'_> {
+        #[cfg(feature = "alloc")]
+        {
+            ConsSet::many(ManyItems::Owned(new_items.into_iter().collect()), self)
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            // without a box to keep the items in, the frame can only hold one of them, just like `extend` does
+            let this = {
+                let mut new = new_items
+                    .into_iter()
+                    .filter(|new_item| !self.contains_ref(new_item));
+                let this = new.next();
+                assert!(
+                    new.all(|new_item| Some(&new_item) == this.as_ref()),
+                    "Alloc-free `ConsSet` frame can only own a single new item, enable `alloc` feature or borrow the items with inherent `ConsSet::extend_many`"
+                );
+                this
+            };
+            ConsSet::con(this, self)
         }
     }
 
     #[inline]
     fn fork(&mut self) -> Self::Shorten<'_> {
-        ConsSet(ConsRepr::Con {
-            this: None,
-            tail: self,
-        })
+        ConsSet::con(None, self)
     }
 
     type IntoIter<'i>
-        = ConsIter<'i, Item>
+        = ConsIter<'i, Item, F>
     where
         Self: 'i;
 
     #[inline]
     fn iter(&self) -> Self::IntoIter<'_> {
-        ConsIter(&self.0)
+        ConsIter {
+            node: &self.holding().0,
+            index: 0,
+        }
+    }
+
+    type Frames<'i>
+        = ConsFrames<'i, Item, F>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn frames(&self) -> Self::Frames<'_> {
+        ConsFrames {
+            node: &self.0,
+            index: 0,
+            yielded: false,
+        }
+    }
+
+    type Path<'i>
+        = ConsPath<'i, Item, F>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn iter_path(&self) -> Self::Path<'_> {
+        ConsPath::new(self)
+    }
+}
+
+/// [`StackedSet::Backend`] of [`ConsSet`]. Items are looked up by walking the list, so any [`PartialEq`] borrowed form will do
+#[derive(Debug)]
+pub struct ConsBackend<Item, F>(PhantomData<fn() -> (Item, F)>);
+
+impl<Item, F> Backend for ConsBackend<Item, F> {
+    type Item = Item;
+
+    type View<'a>
+        = &'a ConsSet<'a, Item, F>
+    where
+        Self: 'a;
+}
+
+impl<Item, F, Q> Query<ConsBackend<Item, F>> for Q
+where
+    Item: PartialEq + Borrow<Q>,
+    F: Fingerprint<Item>,
+    Q: ?Sized + PartialEq,
+{
+    #[inline]
+    fn get_in<'a>(&self, set: &'a ConsSet<'a, Item, F>) -> Option<&'a Item>
+    where
+        ConsBackend<Item, F>: 'a,
+    {
+        let mut node = set.holding();
+        loop {
+            match &node.0 {
+                ConsRepr::Nil => break None,
+                ConsRepr::Con { this, link } => match this {
+                    Some(this) if this.borrow() == self => break Some(this),
+                    _ => node = link.below,
+                },
+                ConsRepr::Many { these, new, link } => {
+                    // items repeated in the slice were not actually added, so outer ones take precedence
+                    match these.iter().position(|this| this.borrow() == self) {
+                        Some(index) if ConsRepr::is_new_in_many(these, *new, link, index) => {
+                            break Some(&these[index]);
+                        }
+                        _ => node = link.below,
+                    }
+                }
+            }
+        }
     }
 }
 
 #[allow(missing_debug_implementations)]
-pub struct ConsIter<'l, Item>(&'l ConsRepr<'l, Item>);
+pub struct ConsIter<'l, Item, F> {
+    // only nodes holding items are visited
+    node: &'l ConsRepr<'l, Item, F>,
+    // position inside of `ConsRepr::Many` node
+    index: usize,
+}
 
-impl<'l, Item> Iterator for ConsIter<'l, Item> {
+impl<'l, Item: PartialEq, F: Fingerprint<Item>> Iterator for ConsIter<'l, Item, F> {
     type Item = &'l Item;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            match core::mem::replace(&mut self.0, &ConsRepr::Nil) {
+            match self.node {
+                ConsRepr::Nil => break None,
+                ConsRepr::Con { this, link } => {
+                    self.node = &link.below.0;
+                    if let Some(item) = this {
+                        break Some(item);
+                    }
+                }
+                ConsRepr::Many { these, new, link } => {
+                    let index = self.index;
+                    if index == these.len() {
+                        self.node = &link.below.0;
+                        self.index = 0;
+                    } else {
+                        self.index += 1;
+                        if ConsRepr::is_new_in_many(these, *new, link, index) {
+                            break Some(&these[index]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[allow(missing_debug_implementations)]
+pub struct ConsFrames<'l, Item, F> {
+    node: &'l ConsRepr<'l, Item, F>,
+    // position inside of `ConsRepr::Many` node, counting from the end
+    index: usize,
+    // whether current `ConsRepr::Many` node has reported any items
+    yielded: bool,
+}
+
+impl<'l, Item: PartialEq, F: Fingerprint<Item>> Iterator for ConsFrames<'l, Item, F> {
+    type Item = (usize, Option<&'l Item>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.node {
                 ConsRepr::Nil => break None,
-                ConsRepr::Con {
-                    this: Some(item),
-                    tail,
-                } => {
-                    self.0 = &tail.0;
-                    break Some(item);
+                ConsRepr::Con { this, link } => {
+                    self.node = &link.tail.0;
+                    break Some((link.depth, this.as_ref()));
+                }
+                ConsRepr::Many { these, new, link } => {
+                    if self.index == these.len() {
+                        self.node = &link.tail.0;
+                        self.index = 0;
+                        // frame that added nothing is a fork
+                        if !core::mem::take(&mut self.yielded) {
+                            break Some((link.depth, None));
+                        }
+                    } else {
+                        let index = these.len() - 1 - self.index;
+                        self.index += 1;
+                        if ConsRepr::is_new_in_many(these, *new, link, index) {
+                            self.yielded = true;
+                            break Some((link.depth, Some(&these[index])));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Number of frames [`ConsPath`] looks up with a single [`ConsSet::nth_ancestor`] call
+const PATH_CHUNK: usize = 32;
+
+#[allow(missing_debug_implementations)]
+pub struct ConsPath<'l, Item, F> {
+    top: &'l ConsSet<'l, Item, F>,
+    // frames yet to be reported, the current one last. Next ones are found by a single jump from the top frame, and a walk down the tails from there
+    chunk: [&'l ConsSet<'l, Item, F>; PATH_CHUNK],
+    chunk_len: usize,
+    // position inside of `ConsRepr::Many` node
+    index: usize,
+    remaining: usize,
+}
+
+impl<'l, Item, F> ConsPath<'l, Item, F> {
+    /// Starts the path from the outermost frame of `top`
+    #[inline]
+    fn new(top: &'l ConsSet<'l, Item, F>) -> Self {
+        let mut path = ConsPath {
+            top,
+            chunk: [top; PATH_CHUNK],
+            chunk_len: 0,
+            index: 0,
+            remaining: top.len_cached(),
+        };
+        path.refill(0);
+        path
+    }
+
+    /// Frame the items are currently reported from
+    #[inline]
+    fn node(&self) -> &'l ConsSet<'l, Item, F> {
+        self.chunk[self.chunk_len - 1]
+    }
+
+    /// Moves on to the frame above the one at `depth`
+    #[inline]
+    fn ascend(&mut self, depth: usize) {
+        self.index = 0;
+        self.chunk_len -= 1;
+        if self.chunk_len == 0 {
+            self.refill(depth);
+        }
+    }
+
+    /// Fills the chunk with frames above the one at `depth`, so that the whole path takes linear number of steps, plus a logarithmic jump per chunk
+    #[inline]
+    fn refill(&mut self, depth: usize) {
+        let top_depth = self.top.depth_cached();
+        let count = top_depth.saturating_sub(depth).min(PATH_CHUNK);
+        if count == 0 {
+            self.remaining = 0;
+            return;
+        }
+        let mut node = self
+            .top
+            .nth_ancestor(top_depth - depth - count)
+            .unwrap_or(self.top);
+        for slot in &mut self.chunk[..count] {
+            *slot = node;
+            node = node.parent().unwrap_or(node);
+        }
+        self.chunk_len = count;
+    }
+}
+
+impl<Item, F> Clone for ConsPath<'_, Item, F> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            top: self.top,
+            chunk: self.chunk,
+            chunk_len: self.chunk_len,
+            index: self.index,
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<'l, Item: PartialEq, F: Fingerprint<Item>> Iterator for ConsPath<'l, Item, F> {
+    type Item = &'l Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.remaining != 0 {
+            match &self.node().0 {
+                ConsRepr::Nil => break,
+                ConsRepr::Con { this, link } => {
+                    // counted before ascending, as there's nothing left above the top frame
+                    self.remaining -= usize::from(this.is_some());
+                    self.ascend(link.depth);
+                    if this.is_some() {
+                        return this.as_ref();
+                    }
                 }
-                ConsRepr::Con { this: None, tail } => {
-                    self.0 = &tail.0;
+                ConsRepr::Many { these, new, link } => {
+                    let index = self.index;
+                    if index == these.len() {
+                        self.ascend(link.depth);
+                    } else {
+                        self.index += 1;
+                        if ConsRepr::is_new_in_many(these, *new, link, index) {
+                            self.remaining -= 1;
+                            return Some(&these[index]);
+                        }
+                    }
                 }
             }
         }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
     }
 }
+
+impl<Item: PartialEq, F: Fingerprint<Item>> ExactSizeIterator for ConsPath<'_, Item, F> {}
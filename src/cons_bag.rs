@@ -0,0 +1,169 @@
+use core::fmt::Debug;
+
+use crate::StackedBag;
+
+/// `Cons list`-like implementation of [`StackedBag`]
+///
+/// Multiplicity of an item is the number of nodes holding it, so counting always walks the whole list
+pub struct ConsBag<'tail, Item>(ConsBagRepr<'tail, Item>);
+
+// In case you are wondering why is this type private - intend is to hide enum variants from public interface
+enum ConsBagRepr<'tail, Item> {
+    Nil,
+    Con {
+        this: Option<Item>,
+        tail: &'tail ConsBag<'tail, Item>,
+        // both counts are cached here, so that they could be answered without walking the list
+        len: usize,
+        depth: usize,
+    },
+}
+
+impl<'tail, Item> ConsBag<'tail, Item> {
+    /// A private constructor for a node on top of `tail`
+    #[inline]
+    fn con(this: Option<Item>, tail: &'tail Self) -> Self {
+        let (len, depth) = match tail.0 {
+            ConsBagRepr::Nil => (0, 0),
+            ConsBagRepr::Con { len, depth, .. } => (len, depth),
+        };
+        ConsBag(ConsBagRepr::Con {
+            len: len + usize::from(this.is_some()),
+            depth: depth + 1,
+            this,
+            tail,
+        })
+    }
+}
+
+impl<Item: PartialEq + Debug> Debug for ConsBag<'_, Item> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<Item: PartialEq> StackedBag for ConsBag<'_, Item> {
+    type Item = Item;
+
+    #[inline]
+    fn empty() -> Self {
+        ConsBag(ConsBagRepr::Nil)
+    }
+
+    #[inline]
+    fn count_ref(&self, item: &Self::Item) -> usize {
+        ConsBagNodes(&self.0).filter(|this| *this == item).count()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        match self.0 {
+            ConsBagRepr::Nil => 0,
+            ConsBagRepr::Con { len, .. } => len,
+        }
+    }
+
+    #[inline]
+    fn depth(&self) -> usize {
+        match self.0 {
+            ConsBagRepr::Nil => 0,
+            ConsBagRepr::Con { depth, .. } => depth,
+        }
+    }
+
+    type Shorten<'new>
+        = ConsBag<'new, Item>
+    where
+        Self: 'new;
+
+    #[inline]
+    fn extend(&mut self, new_item: Item) -> Self::Shorten<'_> {
+        ConsBag::con(Some(new_item), self)
+    }
+
+    #[inline]
+    fn fork(&mut self) -> Self::Shorten<'_> {
+        ConsBag::con(None, self)
+    }
+
+    type IntoIter<'i>
+        = ConsBagIter<'i, Item>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn iter(&self) -> Self::IntoIter<'_> {
+        ConsBagIter {
+            head: &self.0,
+            current: ConsBagNodes(&self.0),
+        }
+    }
+}
+
+/// Iterator over every item node, duplicates included
+struct ConsBagNodes<'l, Item>(&'l ConsBagRepr<'l, Item>);
+
+impl<'l, Item> Iterator for ConsBagNodes<'l, Item> {
+    type Item = &'l Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match core::mem::replace(&mut self.0, &ConsBagRepr::Nil) {
+                ConsBagRepr::Nil => break None,
+                ConsBagRepr::Con { this, tail, .. } => {
+                    self.0 = &tail.0;
+                    if let Some(item) = this {
+                        break Some(item);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[allow(missing_debug_implementations)]
+pub struct ConsBagIter<'l, Item> {
+    head: &'l ConsBagRepr<'l, Item>,
+    current: ConsBagNodes<'l, Item>,
+}
+
+impl<Item: PartialEq> ConsBagIter<'_, Item> {
+    /// Checks if item occurs somewhere between the head and the `position` node
+    #[inline]
+    fn seen_before(&self, position: &ConsBagRepr<'_, Item>, item: &Item) -> bool {
+        let mut node = self.head;
+        while !core::ptr::eq(node, position) {
+            match node {
+                ConsBagRepr::Nil => break,
+                ConsBagRepr::Con { this, tail, .. } => {
+                    if this.as_ref().is_some_and(|this| this == item) {
+                        return true;
+                    }
+                    node = &tail.0;
+                }
+            }
+        }
+        false
+    }
+}
+
+impl<'l, Item: PartialEq> Iterator for ConsBagIter<'l, Item> {
+    type Item = (&'l Item, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let position = self.current.0;
+            let item = self.current.next()?;
+            // only the innermost occurrence is reported, so skip the item if it was already seen closer to the head
+            if !self.seen_before(position, item) {
+                let count = 1 + ConsBagNodes(self.current.0)
+                    .filter(|other| *other == item)
+                    .count();
+                break Some((item, count));
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use core::fmt::Debug;
+
+use crate::StackedMap;
+
+/// `Cons list`-like implementation of [`StackedMap`]
+///
+/// Lookups walk the list from the innermost binding, so inner bindings naturally shadow outer ones
+pub struct ConsMap<'tail, Key, Value>(ConsMapRepr<'tail, Key, Value>);
+
+// In case you are wondering why is this type private - intend is to hide enum variants from public interface
+enum ConsMapRepr<'tail, Key, Value> {
+    Nil,
+    Con {
+        this: Option<(Key, Value)>,
+        tail: &'tail ConsMap<'tail, Key, Value>,
+        // both counts are cached here, so that they could be answered without walking the list
+        len: usize,
+        depth: usize,
+    },
+}
+
+impl<'tail, Key: PartialEq, Value> ConsMap<'tail, Key, Value> {
+    /// A private constructor for a node on top of `tail`
+    #[inline]
+    fn con(this: Option<(Key, Value)>, tail: &'tail Self) -> Self {
+        let (len, depth) = match tail.0 {
+            ConsMapRepr::Nil => (0, 0),
+            ConsMapRepr::Con { len, depth, .. } => (len, depth),
+        };
+        let new_key = this
+            .as_ref()
+            .is_some_and(|(key, _)| !tail.contains_key(key));
+        ConsMap(ConsMapRepr::Con {
+            len: len + usize::from(new_key),
+            depth: depth + 1,
+            this,
+            tail,
+        })
+    }
+}
+
+impl<Key: PartialEq + Debug, Value: Debug> Debug for ConsMap<'_, Key, Value> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<Key: PartialEq, Value> StackedMap for ConsMap<'_, Key, Value> {
+    type Key = Key;
+
+    type Value = Value;
+
+    #[inline]
+    fn empty() -> Self {
+        ConsMap(ConsMapRepr::Nil)
+    }
+
+    #[inline]
+    fn get_ref(&self, key: &Self::Key) -> Option<&Self::Value> {
+        let mut current = &self.0;
+        loop {
+            match current {
+                ConsMapRepr::Nil => break None,
+                ConsMapRepr::Con {
+                    this: Some((this, value)),
+                    ..
+                } if this == key => break Some(value),
+                ConsMapRepr::Con { tail, .. } => current = &tail.0,
+            }
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        match self.0 {
+            ConsMapRepr::Nil => 0,
+            ConsMapRepr::Con { len, .. } => len,
+        }
+    }
+
+    #[inline]
+    fn depth(&self) -> usize {
+        match self.0 {
+            ConsMapRepr::Nil => 0,
+            ConsMapRepr::Con { depth, .. } => depth,
+        }
+    }
+
+    type Shorten<'new>
+        = ConsMap<'new, Key, Value>
+    where
+        Self: 'new;
+
+    #[inline]
+    fn insert(&mut self, key: Key, value: Value) -> Self::Shorten<'_> {
+        ConsMap::con(Some((key, value)), self)
+    }
+
+    #[inline]
+    fn fork(&mut self) -> Self::Shorten<'_> {
+        ConsMap::con(None, self)
+    }
+
+    type IntoIter<'i>
+        = ConsMapIter<'i, Key, Value>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn iter(&self) -> Self::IntoIter<'_> {
+        ConsMapIter {
+            head: &self.0,
+            current: &self.0,
+        }
+    }
+}
+
+#[allow(missing_debug_implementations)]
+pub struct ConsMapIter<'l, Key, Value> {
+    head: &'l ConsMapRepr<'l, Key, Value>,
+    current: &'l ConsMapRepr<'l, Key, Value>,
+}
+
+impl<Key: PartialEq, Value> ConsMapIter<'_, Key, Value> {
+    /// Checks if key is bound somewhere between the head and current node, i.e. if current binding is shadowed
+    #[inline]
+    fn shadowed(&self, key: &Key) -> bool {
+        let mut node = self.head;
+        while !core::ptr::eq(node, self.current) {
+            match node {
+                ConsMapRepr::Nil => break,
+                ConsMapRepr::Con { this, tail, .. } => {
+                    if this.as_ref().is_some_and(|(this, _)| this == key) {
+                        return true;
+                    }
+                    node = &tail.0;
+                }
+            }
+        }
+        false
+    }
+}
+
+impl<'l, Key: PartialEq, Value> Iterator for ConsMapIter<'l, Key, Value> {
+    type Item = (&'l Key, &'l Value);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.current {
+                ConsMapRepr::Nil => break None,
+                ConsMapRepr::Con { this, tail, .. } => {
+                    let shadowed = this.as_ref().is_none_or(|(key, _)| self.shadowed(key));
+                    let this = this.as_ref();
+                    self.current = &tail.0;
+                    if !shadowed {
+                        break this.map(|(key, value)| (key, value));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use core::fmt::{Debug, Display};
+
+/// Error returned by [`StackedSet::enter`](crate::StackedSet::enter), if the item was already present in the set, meaning that a cycle was found
+///
+/// Carries the repeated item along with the [`StackedSet::iter_path`](crate::StackedSet::iter_path) iterator `P`, positioned at the item's first occurrence, so that the cycle can be inspected with [`CycleError::path`]
+pub struct CycleError<Item, P> {
+    item: Item,
+    path: P,
+}
+
+impl<Item, P> CycleError<Item, P> {
+    /// A private constructor, see [`StackedSet::enter`](crate::StackedSet::enter)
+    #[inline]
+    pub(crate) fn new(item: Item, path: P) -> Self {
+        Self { item, path }
+    }
+
+    /// Returns the repeated item, i.e. the one that was attempted to be entered again
+    #[inline]
+    #[must_use]
+    pub fn item(&self) -> &Item {
+        &self.item
+    }
+
+    /// Returns the repeated item, dropping the reference to the set
+    #[inline]
+    #[must_use]
+    pub fn into_item(self) -> Item {
+        self.item
+    }
+}
+
+impl<Item, P: Clone> CycleError<Item, P> {
+    /// Returns iterator over the cycle, i.e. items from the first occurrence of the repeated item (inclusive) to the top of the set, in order they were added
+    ///
+    /// Path is empty if the item is present in the set, but wasn't added by any of its frames (e.g. it was committed with [`CollectionSet::commit`](crate::collection::CollectionSet::commit))
+    #[inline]
+    #[must_use]
+    pub fn path(&self) -> CyclePath<P> {
+        CyclePath(self.path.clone())
+    }
+}
+
+impl<Item: Debug, P: Iterator<Item: Debug> + Clone> Debug for CycleError<Item, P> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CycleError")
+            .field("item", &self.item)
+            .field("path", &self.path())
+            .finish()
+    }
+}
+
+impl<Item: Debug, P: Iterator<Item: Debug> + Clone> Display for CycleError<Item, P> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "cycle detected: ")?;
+        for item in self.path() {
+            write!(f, "{item:?} -> ")?;
+        }
+        write!(f, "{:?}", self.item)
+    }
+}
+
+impl<Item: Debug, P: Iterator<Item: Debug> + Clone> core::error::Error for CycleError<Item, P> {}
+
+/// Iterator over the cycle path, see [`CycleError::path`]
+#[derive(Clone)]
+pub struct CyclePath<P>(P);
+
+impl<P: Iterator<Item: Debug> + Clone> Debug for CyclePath<P> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.0.clone()).finish()
+    }
+}
+
+impl<P: Iterator> Iterator for CyclePath<P> {
+    type Item = P::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<P: ExactSizeIterator> ExactSizeIterator for CyclePath<P> {}
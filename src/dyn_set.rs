@@ -0,0 +1,85 @@
+use crate::StackedSet;
+
+/// Object-safe companion of [`StackedSet`], allowing dynamic dispatch via `&mut dyn DynStackedSet<Item = ...>`
+///
+/// Every [`StackedSet`] implements this trait, so there's no need to implement it manually. Methods are suffixed with `_dyn` to avoid ambiguity with [`StackedSet`] ones.
+///
+/// Since frames cannot be returned from a trait object, [`DynStackedSet::extend_dyn`] and [`DynStackedSet::fork_dyn`] pass them to a callback instead
+pub trait DynStackedSet {
+    /// Item stored in the set
+    type Item;
+
+    /// Checks if element is present in the set. See [`StackedSet::contains_ref`]
+    fn contains_dyn(&self, item: &Self::Item) -> bool;
+
+    /// Number of items present in the set. See [`StackedSet::len`]
+    fn len_dyn(&self) -> usize;
+
+    /// Number of frames this set consists of. See [`StackedSet::depth`]
+    fn depth_dyn(&self) -> usize;
+
+    /// Calls the closure on every item in the set, no specific order guaranteed. See [`StackedSet::iter`]
+    fn for_each_dyn(&self, f: &mut dyn FnMut(&Self::Item));
+
+    /// Returns boxed iterator over the set, no specific order guaranteed. See [`StackedSet::iter`]
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn iter_boxed(&self) -> alloc::boxed::Box<dyn Iterator<Item = &Self::Item> + '_>;
+
+    /// Extends the set with an item and passes the result to the closure. Returns `true`, if the item was not present in the set before. See [`StackedSet::try_extend`]
+    fn extend_dyn(
+        &mut self,
+        new_item: Self::Item,
+        f: &mut dyn FnMut(&mut dyn DynStackedSet<Item = Self::Item>),
+    ) -> bool;
+
+    /// Forks the set and passes the result to the closure. See [`StackedSet::fork`]
+    fn fork_dyn(&mut self, f: &mut dyn FnMut(&mut dyn DynStackedSet<Item = Self::Item>));
+}
+
+impl<S: StackedSet> DynStackedSet for S {
+    type Item = S::Item;
+
+    #[inline]
+    fn contains_dyn(&self, item: &Self::Item) -> bool {
+        self.contains_ref(item)
+    }
+
+    #[inline]
+    fn len_dyn(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn depth_dyn(&self) -> usize {
+        self.depth()
+    }
+
+    #[inline]
+    fn for_each_dyn(&self, f: &mut dyn FnMut(&Self::Item)) {
+        self.iter().for_each(f);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn iter_boxed(&self) -> alloc::boxed::Box<dyn Iterator<Item = &Self::Item> + '_> {
+        alloc::boxed::Box::new(self.iter())
+    }
+
+    #[inline]
+    fn extend_dyn(
+        &mut self,
+        new_item: Self::Item,
+        f: &mut dyn FnMut(&mut dyn DynStackedSet<Item = Self::Item>),
+    ) -> bool {
+        let extended = self.try_extend(new_item);
+        let inserted = extended.is_inserted();
+        f(&mut extended.into_inner());
+        inserted
+    }
+
+    #[inline]
+    fn fork_dyn(&mut self, f: &mut dyn FnMut(&mut dyn DynStackedSet<Item = Self::Item>)) {
+        f(&mut self.fork());
+    }
+}
@@ -0,0 +1,109 @@
+use core::hash::{Hash, Hasher};
+
+/// Accumulated summary of the items in a [`StackCons`](crate::StackCons) node and all the nodes below it, allowing to reject most of the absent items without walking the list
+///
+/// False positives are fine (the list is walked then), false negatives are not: if `union` of fingerprints includes an item's fingerprint, `may_contain` must return `true`
+pub trait Fingerprint<Item>: Copy {
+    /// Fingerprint of an empty set
+    const EMPTY: Self;
+
+    /// Computes fingerprint of a single item. Equal items must have equal fingerprints
+    fn of(item: &Item) -> Self;
+
+    /// Combines two fingerprints, so that the result may contain items of both
+    #[must_use]
+    fn union(self, other: Self) -> Self;
+
+    /// Checks if items with `item` fingerprint may be summarized by this one. `false` means the item is definitely absent
+    fn may_contain(self, item: Self) -> bool;
+}
+
+/// [`Fingerprint`] that summarizes nothing, so every lookup walks the list. Takes no space and requires nothing from the items
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoFingerprint;
+
+impl<Item> Fingerprint<Item> for NoFingerprint {
+    const EMPTY: Self = NoFingerprint;
+
+    #[inline]
+    fn of(_item: &Item) -> Self {
+        NoFingerprint
+    }
+
+    #[inline]
+    fn union(self, _other: Self) -> Self {
+        NoFingerprint
+    }
+
+    #[inline]
+    fn may_contain(self, _item: Self) -> bool {
+        true
+    }
+}
+
+/// FNV-1a hasher, as `core` provides none
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// Hashes the item with FNV-1a
+#[inline]
+fn fnv<Item: Hash>(item: &Item) -> u64 {
+    let mut hasher = Fnv(0xcbf2_9ce4_8422_2325);
+    item.hash(&mut hasher);
+    hasher.finish()
+}
+
+macro_rules! bloom {
+    ($(#[$meta:meta])* $name:ident($bits:ty)) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        pub struct $name($bits);
+
+        impl<Item: Hash> Fingerprint<Item> for $name {
+            const EMPTY: Self = $name(0);
+
+            #[inline]
+            fn of(item: &Item) -> Self {
+                // two bits per item, taken from different parts of the hash
+                let hash = fnv(item);
+                let width = u64::from(<$bits>::BITS);
+                $name((1 << (hash % width)) | (1 << ((hash >> 32) % width)))
+            }
+
+            #[inline]
+            fn union(self, other: Self) -> Self {
+                $name(self.0 | other.0)
+            }
+
+            #[inline]
+            fn may_contain(self, item: Self) -> bool {
+                self.0 & item.0 == item.0
+            }
+        }
+    };
+}
+
+bloom!(
+    /// 64-bit Bloom filter [`Fingerprint`]. Requires items to be [`Hash`], and [`Hash`] to agree with [`PartialEq`]
+    ///
+    /// Filter saturates as the set grows, so it's most effective for sets of up to few dozens of items
+    Bloom64(u64)
+);
+
+bloom!(
+    /// 128-bit Bloom filter [`Fingerprint`], same as [`Bloom64`], but saturating twice as slow
+    Bloom128(u128)
+);
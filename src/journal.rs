@@ -0,0 +1,150 @@
+use alloc::vec::Vec;
+use core::{fmt::Debug, ops::Deref};
+
+use crate::collection::{CollectionSet, SetCollection};
+
+/// Non-lexically scoped view of a [`CollectionSet`] frame, created with [`CollectionSet::journal`]
+///
+/// Items are added with [`Journal::insert`] and removed back with [`Journal::rollback_to`], in whatever order the caller's control flow needs, e.g. when scopes are pushed and popped in an explicit loop rather than through recursion. Every addition is recorded in an undo log, so on drop the journal removes all the items it has added
+pub struct Journal<'l, Collection: SetCollection> {
+    // journal is a fork frame of its own, so items it adds are never mistaken for items of the parent frames
+    set: CollectionSet<'l, Collection>,
+    log: Vec<Entry<Collection::ExtendMemory>>,
+    // total number of insertions, used to tell apart log entries that took the place of rolled back ones
+    inserted: usize,
+}
+
+struct Entry<Memory> {
+    memory: Memory,
+    serial: usize,
+}
+
+/// A point in the [`Journal`] history to roll back to, see [`Journal::checkpoint`]. Only meaningful for the journal it was taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    len: usize,
+    inserted: usize,
+}
+
+/// Error returned on an attempt to use a [`Checkpoint`], which was already rolled back past
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaleCheckpoint;
+
+impl core::fmt::Display for StaleCheckpoint {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("journal was rolled back past the checkpoint")
+    }
+}
+
+impl core::error::Error for StaleCheckpoint {}
+
+impl<'l, Collection: SetCollection> Journal<'l, Collection> {
+    /// A private constructor, see [`CollectionSet::journal`]
+    #[inline]
+    pub(crate) fn new(set: CollectionSet<'l, Collection>) -> Self {
+        Self {
+            set,
+            log: Vec::new(),
+            inserted: 0,
+        }
+    }
+
+    /// Remembers current state of the journal, so that it could be restored later with [`Journal::rollback_to`]
+    #[inline]
+    #[must_use]
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            len: self.log.len(),
+            inserted: self.inserted,
+        }
+    }
+
+    /// Adds an item to the set, returning whether it was actually added, i.e. it was not present before. Already present items are not recorded, so rolling back will not remove them
+    #[inline]
+    pub fn insert(&mut self, new_item: Collection::Item) -> bool {
+        let Some(memory) = self.set.c_mut().try_extend(new_item) else {
+            return false;
+        };
+        self.log.push(Entry {
+            memory,
+            serial: self.inserted,
+        });
+        self.inserted += 1;
+        true
+    }
+
+    /// Removes every item added after the checkpoint was taken
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StaleCheckpoint`] if the journal was already rolled back to an earlier point, so that items added since are unknown. Journal is left intact in that case
+    #[inline]
+    pub fn rollback_to(&mut self, checkpoint: Checkpoint) -> Result<(), StaleCheckpoint> {
+        self.check(checkpoint)?;
+        let collection = self.set.c_mut();
+        for entry in self.log.drain(checkpoint.len..).rev() {
+            collection.remove(entry.memory);
+        }
+        Ok(())
+    }
+
+    /// Returns iterator over items added after the checkpoint was taken, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StaleCheckpoint`] under the same conditions as [`Journal::rollback_to`]
+    #[inline]
+    pub fn added_since(
+        &self,
+        checkpoint: Checkpoint,
+    ) -> Result<impl Iterator<Item = &Collection::Item>, StaleCheckpoint> {
+        self.check(checkpoint)?;
+        let collection: &Collection = &self.set;
+        Ok(self.log[checkpoint.len..]
+            .iter()
+            .map(|entry| collection.extended_item(&entry.memory)))
+    }
+
+    /// Checks that the entries recorded before the checkpoint was taken are still in the log
+    #[inline]
+    fn check(&self, checkpoint: Checkpoint) -> Result<(), StaleCheckpoint> {
+        let valid = match checkpoint.len.checked_sub(1) {
+            None => true,
+            Some(last) => self
+                .log
+                .get(last)
+                .is_some_and(|entry| entry.serial < checkpoint.inserted),
+        };
+        valid.then_some(()).ok_or(StaleCheckpoint)
+    }
+}
+
+impl<'l, Collection: SetCollection> Deref for Journal<'l, Collection> {
+    type Target = CollectionSet<'l, Collection>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.set
+    }
+}
+
+impl<Collection: SetCollection + Debug> Debug for Journal<'_, Collection> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Journal")
+            .field("set", &self.set)
+            .field("logged", &self.log.len())
+            .finish()
+    }
+}
+
+impl<Collection: SetCollection> Drop for Journal<'_, Collection> {
+    #[inline]
+    fn drop(&mut self) {
+        let collection = self.set.c_mut();
+        for entry in self.log.drain(..).rev() {
+            collection.remove(entry.memory);
+        }
+    }
+}
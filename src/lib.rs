@@ -6,12 +6,12 @@
     doc = "(no docs here)"
 )]
 
-use core::borrow::Borrow;
+use core::{borrow::Borrow, ops::RangeBounds};
 
-#[cfg(feature = "std-hash")]
+#[cfg(any(test, feature = "std-hash"))]
 extern crate std;
 
-#[cfg(any(feature = "alloc-vec", feature = "alloc-tree"))]
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
 /// Defines implementation of [`StackedSet`] based on normal kind of collection.
@@ -19,10 +19,63 @@ extern crate alloc;
 #[cfg_attr(docsrs, doc(cfg(feature = "collection")))]
 pub mod collection;
 
+/// Defines implementation of [`StackedMap`] based on normal kind of map collection.
+#[cfg(feature = "collection")]
+#[cfg_attr(docsrs, doc(cfg(feature = "collection")))]
+pub mod collection_map;
+
+/// Defines implementation of [`StackedBag`] based on normal kind of counting collection.
+#[cfg(feature = "collection")]
+#[cfg_attr(docsrs, doc(cfg(feature = "collection")))]
+pub mod collection_bag;
+
+mod algebra;
+
 #[cfg(feature = "cons")]
 #[doc(hidden)]
 mod cons;
 
+#[cfg(feature = "cons")]
+#[doc(hidden)]
+mod fingerprint;
+
+#[doc(hidden)]
+mod dyn_set;
+
+#[doc(hidden)]
+mod cycle;
+
+#[doc(hidden)]
+mod bounded;
+
+#[cfg(feature = "cons")]
+#[doc(hidden)]
+mod cons_bag;
+
+#[cfg(feature = "cons")]
+#[doc(hidden)]
+mod cons_map;
+
+#[cfg(all(feature = "collection", feature = "alloc"))]
+#[doc(hidden)]
+mod journal;
+
+#[cfg(any(feature = "array", feature = "slice"))]
+#[doc(hidden)]
+mod buffer;
+
+#[cfg(feature = "array")]
+#[doc(hidden)]
+mod array;
+
+#[cfg(feature = "slice")]
+#[doc(hidden)]
+mod slice;
+
+#[cfg(feature = "bitset")]
+#[doc(hidden)]
+mod bitset;
+
 #[cfg(feature = "alloc-vec")]
 #[doc(hidden)]
 mod alloc_vec;
@@ -31,15 +84,26 @@ mod alloc_vec;
 #[doc(hidden)]
 mod alloc_tree;
 
+#[cfg(feature = "alloc-sorted")]
+#[doc(hidden)]
+mod alloc_sorted;
+
 #[cfg(feature = "std-hash")]
 #[doc(hidden)]
 mod std_hash;
 
+#[cfg(feature = "alloc-hash")]
+#[doc(hidden)]
+mod alloc_hash;
+
 /// Common trait for stacked set implementations. Users are intended to define their input as `impl StackedSet<Item = WhateverItemTheyNeed>`, so it's up to the user to pick the implementation
 pub trait StackedSet: Sized {
     /// Item stored in the set
     type Item;
 
+    /// Lifetime-free part of the implementation, shared by every frame of the set. Decides, which borrowed forms items can be looked up by, see [`Query`]
+    type Backend: Backend<Item = Self::Item>;
+
     /// Creates an empty set
     #[must_use = "Creating empty set is usually a no-op"]
     fn empty() -> Self;
@@ -53,8 +117,42 @@ pub trait StackedSet: Sized {
     #[must_use = "Checking for presence does not change set contents"]
     fn contains_ref(&self, item: &Self::Item) -> bool;
 
+    /// Checks if element is present in the set, looking it up by it's borrowed form - for example, `&str` can be used to query a set of `String`s without allocating
+    ///
+    /// This is the same approach `std` collections take, except bounds on `Q` depend on the implementation. Generic code can spell them as `str: Query<S::Backend>`, which holds for every frame of the set
+    #[inline]
+    #[must_use = "Checking for presence does not change set contents"]
+    fn contains_by<Q: ?Sized + Query<Self::Backend>>(&self, item: &Q) -> bool {
+        self.get(item).is_some()
+    }
+
+    /// Returns the item stored in the set, that is equal to the provided one. Lookup is done the same way as in [`StackedSet::contains_by`]
+    ///
+    /// This is useful, if items carry some payload that does not take part in equality - for example, where the item was first added
+    #[must_use = "Getting an item does not change set contents"]
+    fn get<Q: ?Sized + Query<Self::Backend>>(&self, item: &Q) -> Option<&Self::Item>;
+
+    /// Number of items present in the set
+    ///
+    /// Implementations are expected to answer this in constant time, i.e. without walking the set
+    #[must_use = "Checking the length does not change set contents"]
+    fn len(&self) -> usize;
+
+    /// Checks if the set contains no items at all
+    #[inline]
+    #[must_use = "Checking the length does not change set contents"]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of frames this set consists of, i.e. number of [`StackedSet::extend`] and [`StackedSet::fork`] calls it took to produce this instance from [`StackedSet::empty`]
+    ///
+    /// Note that forks are counted too, so depth is never less than [`StackedSet::len`]
+    #[must_use = "Checking the depth does not change set contents"]
+    fn depth(&self) -> usize;
+
     /// Intended to be the same type, but living for less time
-    type Shorten<'new>: StackedSet<Item = Self::Item> + 'new
+    type Shorten<'new>: StackedSet<Item = Self::Item, Backend = Self::Backend> + 'new
     where
         Self: 'new;
 
@@ -65,8 +163,49 @@ pub trait StackedSet: Sized {
     /// - parent itself cannot be observed while new instance lives
     ///
     /// Once this new instance is dropped, original stack is not supposed to contain this new type
+    #[inline]
     #[must_use = "Provided value is only contained in a set returned form this call. Despite requiring exclusive borrow, original set is should not be expected to change. Check documentation for more details."]
-    fn extend(&mut self, new_item: Self::Item) -> Self::Shorten<'_>;
+    fn extend(&mut self, new_item: Self::Item) -> Self::Shorten<'_> {
+        self.try_extend(new_item).into_inner()
+    }
+
+    /// Same as [`StackedSet::extend`], but trusts the caller that the item is not present in the set, letting implementations skip the lookup they otherwise do. Useful when presence was just checked, or items are unique by construction (like increasing counters)
+    ///
+    /// Extending the set with an already present item is not unsafe, but leaves the set in unspecified state: it may report wrong [`StackedSet::len`], yield the item twice, or even lose it once the returned instance is dropped
+    ///
+    /// # Panics
+    ///
+    /// Panics if the item is already present, but only when debug assertions are enabled
+    #[inline]
+    #[must_use = "Provided value is only contained in a set returned form this call. Despite requiring exclusive borrow, original set is should not be expected to change. Check documentation for more details."]
+    fn extend_unique(&mut self, new_item: Self::Item) -> Self::Shorten<'_> {
+        debug_assert!(
+            !self.contains_ref(&new_item),
+            "Item passed to `extend_unique` is already present in the set"
+        );
+        self.extend(new_item)
+    }
+
+    /// Same as [`StackedSet::extend`], but also reports if the item was actually added, or it was present in the set already (in which case returned instance is basically a [`StackedSet::fork`])
+    ///
+    /// Implementations are expected to perform a single lookup here, so prefer this method over a [`StackedSet::contains`] + [`StackedSet::extend`] pair
+    #[must_use = "Provided value is only contained in a set returned form this call. Despite requiring exclusive borrow, original set is should not be expected to change. Check documentation for more details."]
+    fn try_extend(&mut self, new_item: Self::Item) -> Extended<Self::Shorten<'_>>;
+
+    /// Same as [`StackedSet::extend`], but adds all the provided items at once, producing a single new instance
+    ///
+    /// Since resulting instance only borrows the original one (and the items), it can be returned from a function - something that chained [`StackedSet::extend`] calls cannot do. Items already present in the set (or repeated among the new ones) are simply not added once again
+    ///
+    /// Once this new instance is dropped, original stack is not supposed to contain any of the new items
+    ///
+    /// [`StackCons`] keeps the items in a boxed slice of the new frame, which needs `alloc` feature. To stay alloc-free, borrow them with inherent [`ConsSet::extend_many`] instead
+    ///
+    /// # Panics
+    ///
+    /// Without `alloc` feature, [`StackCons`] panics if more than one of the items is new to the set
+    #[must_use = "Provided values are only contained in a set returned form this call. Despite requiring exclusive borrow, original set is should not be expected to change. Check documentation for more details."]
+    fn extend_many(&mut self, new_items: impl IntoIterator<Item = Self::Item>)
+    -> Self::Shorten<'_>;
 
     /// Same as [`StackedSet::extend`], but does not actually extend the stackset
     ///
@@ -74,18 +213,518 @@ pub trait StackedSet: Sized {
     #[must_use = "Despite requiring exclusive borrow, original set is should not be expected to change. Check documentation for more details."]
     fn fork(&mut self) -> Self::Shorten<'_>;
 
+    /// Extends the set with an item, unless it's already present. Intended for guarding recursion: once an item is entered again, a cycle is reported along with its path (see [`CycleError::path`])
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CycleError`] if the item is already present in the set
+    #[inline]
+    fn enter(
+        &mut self,
+        new_item: Self::Item,
+    ) -> Result<Self::Shorten<'_>, CycleError<Self::Item, Self::Path<'_>>>
+    where
+        Self::Item: PartialEq,
+    {
+        if !self.contains_ref(&new_item) {
+            // presence was just checked, no need to look the item up once again
+            return Ok(self.extend_unique(new_item));
+        }
+        let mut path = self.iter_path();
+        // item not added by any frame yields an empty path
+        let start = self
+            .position(|item| *item == new_item)
+            .unwrap_or(path.len());
+        if let Some(before) = start.checked_sub(1) {
+            let _ = path.nth(before);
+        }
+        Err(CycleError::new(new_item, path))
+    }
+
+    /// Calls the closure with the set extended by an item, returning it's result. Extended set is dropped right after the call
+    ///
+    /// This is a convenience wrapper around [`StackedSet::extend`], letting recursive code avoid naming [`StackedSet::Shorten`] types
+    #[inline]
+    fn with_extended<R>(
+        &mut self,
+        new_item: Self::Item,
+        f: impl FnOnce(&mut Self::Shorten<'_>) -> R,
+    ) -> R {
+        f(&mut self.extend(new_item))
+    }
+
+    /// Same as [`StackedSet::with_extended`], but only calls the closure if the item was not present in the set already. Returns `None` otherwise
+    ///
+    /// This turns a typical recursion guard into a one-liner
+    #[inline]
+    fn with_extended_if_absent<R>(
+        &mut self,
+        new_item: Self::Item,
+        f: impl FnOnce(&mut Self::Shorten<'_>) -> R,
+    ) -> Option<R> {
+        match self.try_extend(new_item) {
+            Extended::Inserted(mut inner) => Some(f(&mut inner)),
+            Extended::AlreadyPresent(_) => None,
+        }
+    }
+
+    /// Calls the closure with a fork of the set, returning it's result. See [`StackedSet::with_extended`]
+    #[inline]
+    fn with_fork<R>(&mut self, f: impl FnOnce(&mut Self::Shorten<'_>) -> R) -> R {
+        f(&mut self.fork())
+    }
+
     /// Iterator type for the set
     type IntoIter<'i>: Iterator<Item = &'i Self::Item> + 'i
     where
         Self: 'i;
 
-    /// Returns iterator over the set, no specific order guaranteed
+    /// Returns iterator over the set, no specific order guaranteed (see [`StackedSet::iter_path`], if insertion order is needed)
+    fn iter(&self) -> Self::IntoIter<'_>;
+
+    /// Iterator type for the set frames
+    type Frames<'i>: Iterator<Item = (usize, Option<&'i Self::Item>)> + 'i
+    where
+        Self: 'i;
+
+    /// Returns iterator over frames this set consists of, from the innermost (this one) to the outermost, along with their depth (see [`StackedSet::depth`])
+    ///
+    /// Each frame yields an item it has added, or `None` if it's a fork (including the ones produced by extending with an already present item). A frame produced by [`StackedSet::extend_many`] yields every item it has added (latest first) with the same depth. Empty set itself is not a frame, so it yields nothing
+    fn frames(&self) -> Self::Frames<'_>;
+
+    /// Iterator type over the set items in order they were added
+    type Path<'i>: Iterator<Item = &'i Self::Item> + ExactSizeIterator + Clone + 'i
+    where
+        Self: 'i;
+
+    /// Returns iterator over the set items in order they were added, oldest first
+    ///
+    /// Frames only link to the outer ones, so implementations either rely on the order items are stored in, or skip through the frames. Generic [`PathIter`] walks the frames once per item, taking quadratic time, and is only meant as a fallback
+    fn iter_path(&self) -> Self::Path<'_>;
+
+    /// Returns iterator over the set items in reversed order they were added, i.e. latest first
+    #[inline]
+    fn iter_path_rev(&self) -> impl Iterator<Item = &Self::Item> {
+        self.frames().filter_map(|(_, item)| item)
+    }
+
+    /// Returns an item added by this frame, if any. For frames produced by [`StackedSet::extend_many`], the last added item is returned
+    #[inline]
+    #[must_use = "Inspecting the frame does not change set contents"]
+    fn top(&self) -> Option<&Self::Item> {
+        self.frames().next().and_then(|(_, item)| item)
+    }
+
+    /// Checks if this frame is a fork, i.e. it did not add any items to the set. Empty set is not considered a fork
+    #[inline]
+    #[must_use = "Inspecting the frame does not change set contents"]
+    fn is_fork(&self) -> bool {
+        self.depth() != 0 && self.top().is_none()
+    }
+
+    /// Checks if at least one item of the set satisfies the predicate
+    #[inline]
+    fn any(&self, predicate: impl FnMut(&Self::Item) -> bool) -> bool {
+        self.find(predicate).is_some()
+    }
+
+    /// Returns an item of the set satisfying the predicate, if any. No specific order guaranteed, so if several items match, any one of them could be returned
+    #[inline]
+    fn find(&self, mut predicate: impl FnMut(&Self::Item) -> bool) -> Option<&Self::Item> {
+        self.iter().find(|item| predicate(item))
+    }
+
+    /// Returns an item of the set within the `range` and satisfying the predicate, if any. Useful for structured items, e.g. looking up a `(node, state)` pair by `node` only
+    ///
+    /// Implementations storing items in order are able to visit only the items within the `range`, others are filtering every item of the set. Those may panic on a range starting after its end, just like [`BTreeSet::range`](https://doc.rust-lang.org/alloc/collections/btree_set/struct.BTreeSet.html#method.range) does
+    #[inline]
+    fn find_in_range<Q, R>(
+        &self,
+        range: R,
+        mut predicate: impl FnMut(&Self::Item) -> bool,
+    ) -> Option<&Self::Item>
+    where
+        Self::Item: Borrow<Q>,
+        Q: ?Sized + Ord,
+        R: RangeBounds<Q>,
+    {
+        self.find(|item| range.contains(item.borrow()) && predicate(item))
+    }
+
+    /// Returns position of an item satisfying the predicate in [`StackedSet::iter_path`] order (i.e. `0` for the oldest item). If several items match, the latest added one is reported, as the frames are searched from the innermost one
+    #[inline]
+    fn position(&self, predicate: impl FnMut(&Self::Item) -> bool) -> Option<usize> {
+        let mut path = self.iter_path_rev();
+        path.position(predicate)?;
+        // items older than the found one are the ones left
+        Some(path.count())
+    }
+
+    /// Describes how this set answers membership queries. Set-algebra methods (like [`StackedSet::is_disjoint`]) use this to decide which set to iterate over, and which one to probe
+    #[inline]
+    #[must_use]
+    fn lookup(&self) -> Lookup {
+        Lookup::Linear
+    }
+
+    /// Checks if every provided item is present in the set
+    #[inline]
+    fn contains_all<I>(&self, items: I) -> bool
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Self::Item>,
+    {
+        items
+            .into_iter()
+            .all(|item| self.contains_ref(item.borrow()))
+    }
+
+    /// Checks if at least one of provided items is present in the set
+    #[inline]
+    fn contains_any<I>(&self, items: I) -> bool
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Self::Item>,
+    {
+        items
+            .into_iter()
+            .any(|item| self.contains_ref(item.borrow()))
+    }
+
+    /// Checks if every item of this set is present in the `other` one. Sets don't have to be of the same type
+    #[inline]
+    #[must_use]
+    fn is_subset_of(&self, other: &impl StackedSet<Item = Self::Item>) -> bool {
+        algebra::is_subset(self, other)
+    }
+
+    /// Checks if every item of the `other` set is present in this one. See [`StackedSet::is_subset_of`]
+    #[inline]
+    #[must_use]
+    fn is_superset_of(&self, other: &impl StackedSet<Item = Self::Item>) -> bool {
+        other.is_subset_of(self)
+    }
+
+    /// Checks if this set has no items in common with the `other` one. Sets don't have to be of the same type
+    ///
+    /// Smaller set (or the one with slower [`StackedSet::lookup`]) is iterated over, while the other one is probed
+    #[inline]
+    #[must_use]
+    fn is_disjoint(&self, other: &impl StackedSet<Item = Self::Item>) -> bool {
+        algebra::is_disjoint(self, other)
+    }
+
+    /// Returns iterator over items of this set, that are present in the `other` one as well. Sets don't have to be of the same type
+    ///
+    /// This set is iterated over, while the `other` one is probed, so prefer calling this on the smaller set
+    #[inline]
+    fn intersection<'a>(
+        &'a self,
+        other: &'a impl StackedSet<Item = Self::Item>,
+    ) -> impl Iterator<Item = &'a Self::Item> {
+        self.iter().filter(|item| other.contains_ref(item))
+    }
+}
+
+/// Lifetime-free part of a [`StackedSet`] implementation, see [`StackedSet::Backend`]. For [`CollectionSet`](collection::CollectionSet), it's the collection itself
+pub trait Backend {
+    /// Item stored in the set
+    type Item;
+
+    /// Borrowed frame of the set, that items are looked up in
+    type View<'a>
+    where
+        Self: 'a;
+}
+
+/// Borrowed form of items, that sets built on `B` can be looked up by, see [`StackedSet::get`]. Much like `hashbrown`'s `Equivalent`, it's implemented for the query type, so that every backend asks for the bounds its native lookup needs
+///
+/// Backends walking the items (cons list, `Vec` and buffers) only need [`PartialEq`], sorted ones need [`Ord`], while hashed ones need [`Hash`](core::hash::Hash) + [`Eq`]. In every case the item has to implement [`Borrow`] of the query type, except for `AllocHash`, which takes any `hashbrown::Equivalent` form. Bitset-accelerated sets also ask the query for its `BitIndex`, so that a miss is a single bit test
+pub trait Query<B: Backend + ?Sized> {
+    /// Returns the item equal to this one, if it's present in the view
+    fn get_in<'a>(&self, view: B::View<'a>) -> Option<&'a B::Item>
+    where
+        B: 'a;
+}
+
+/// Describes, how a [`StackedSet`] answers membership queries, see [`StackedSet::lookup`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lookup {
+    /// Lookup walks the items, taking linear time
+    Linear,
+    /// Lookup takes logarithmic time, and [`StackedSet::iter`] yields items in ascending order
+    Sorted,
+    /// Lookup takes (amortized) constant time
+    Hashed,
+}
+
+/// Outcome of [`StackedSet::try_extend`] call
+#[derive(Debug)]
+pub enum Extended<S> {
+    /// Item was not present before, and the new instance is the one containing it
+    Inserted(S),
+    /// Item was present already, so the new instance is basically a [`StackedSet::fork`]
+    AlreadyPresent(S),
+}
+
+impl<S> Extended<S> {
+    /// Returns the new instance regardless of the outcome
+    #[inline]
+    pub fn into_inner(self) -> S {
+        match self {
+            Extended::Inserted(s) | Extended::AlreadyPresent(s) => s,
+        }
+    }
+
+    /// Checks if the item was actually added
+    #[inline]
+    #[must_use]
+    pub fn is_inserted(&self) -> bool {
+        matches!(self, Extended::Inserted(_))
+    }
+
+    /// Checks if the item was present already
+    #[inline]
+    #[must_use]
+    pub fn is_already_present(&self) -> bool {
+        matches!(self, Extended::AlreadyPresent(_))
+    }
+}
+
+/// Iterator over [`StackedSet`] items in order they were added, walking the frames once per item. A fallback for [`StackedSet::iter_path`], taking quadratic time
+#[derive(Debug)]
+pub struct PathIter<'i, S> {
+    set: &'i S,
+    remaining: usize,
+}
+
+impl<'i, S: StackedSet> PathIter<'i, S> {
+    /// Creates iterator over the set items, see [`StackedSet::iter_path`]
+    #[inline]
+    #[must_use]
+    pub fn new(set: &'i S) -> Self {
+        Self {
+            remaining: set.iter_path_rev().count(),
+            set,
+        }
+    }
+}
+
+impl<S> Clone for PathIter<'_, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            set: self.set,
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<'i, S: StackedSet> Iterator for PathIter<'i, S> {
+    type Item = &'i S::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining = self.remaining.checked_sub(1)?;
+        self.set.iter_path_rev().nth(self.remaining)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<S: StackedSet> ExactSizeIterator for PathIter<'_, S> {}
+
+/// Companion of [`StackedSet`], associating a value with each key. Users are intended to define their input as `impl StackedMap<Key = Name, Value = Binding>`, much like with [`StackedSet`]
+///
+/// Lifetime discipline is exactly the same, as in [`StackedSet`]: [`StackedMap::insert`] borrows the map and produces a new frame, containing the binding until it's dropped. Inserting a key that is already present *shadows* the previous value, which becomes visible again, once inner frame is dropped
+pub trait StackedMap: Sized {
+    /// Key type of the map
+    type Key;
+
+    /// Value type of the map
+    type Value;
+
+    /// Creates an empty map
+    #[must_use = "Creating empty map is usually a no-op"]
+    fn empty() -> Self;
+
+    /// Returns value currently bound to the key, if any
+    #[inline]
+    fn get(&self, key: impl Borrow<Self::Key>) -> Option<&Self::Value> {
+        self.get_ref(key.borrow())
+    }
+
+    /// Returns value currently bound to the key, if any
+    #[must_use = "Getting a value does not change map contents"]
+    fn get_ref(&self, key: &Self::Key) -> Option<&Self::Value>;
+
+    /// Checks if key is bound in the map
+    #[inline]
+    #[must_use = "Checking for presence does not change map contents"]
+    fn contains_key(&self, key: impl Borrow<Self::Key>) -> bool {
+        self.get_ref(key.borrow()).is_some()
+    }
+
+    /// Number of distinct keys bound in the map (shadowed bindings are not counted)
+    #[must_use = "Checking the length does not change map contents"]
+    fn len(&self) -> usize;
+
+    /// Checks if the map contains no bindings at all
+    #[inline]
+    #[must_use = "Checking the length does not change map contents"]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of frames this map consists of, forks included. See [`StackedSet::depth`]
+    #[must_use = "Checking the depth does not change map contents"]
+    fn depth(&self) -> usize;
+
+    /// Intended to be the same type, but living for less time. See [`StackedSet::Shorten`]
+    type Shorten<'new>: StackedMap<Key = Self::Key, Value = Self::Value> + 'new
+    where
+        Self: 'new;
+
+    /// Binds the value to the key, returning a new instance now "containing" the binding
+    ///
+    /// If the key was already bound, new value shadows the old one. Once returned instance is dropped, original map is not supposed to contain the new binding (but still contains the old one, if there was any)
+    #[must_use = "Provided binding is only contained in a map returned form this call. Despite requiring exclusive borrow, original map is should not be expected to change. Check documentation for more details."]
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Self::Shorten<'_>;
+
+    /// Same as [`StackedMap::insert`], but does not actually insert anything. See [`StackedSet::fork`]
+    #[must_use = "Despite requiring exclusive borrow, original map is should not be expected to change. Check documentation for more details."]
+    fn fork(&mut self) -> Self::Shorten<'_>;
+
+    /// Iterator type for the map
+    type IntoIter<'i>: Iterator<Item = (&'i Self::Key, &'i Self::Value)> + 'i
+    where
+        Self: 'i;
+
+    /// Returns iterator over currently visible bindings (shadowed ones are skipped), no specific order guaranteed
     fn iter(&self) -> Self::IntoIter<'_>;
 }
 
+/// Counted version of [`StackedSet`] (a multiset), remembering how many times each item was added on the current path
+///
+/// Unlike [`StackedSet::extend`], [`StackedBag::extend`] never turns a duplicate into a fork - item's multiplicity is increased instead, and decreased back once the frame is dropped
+pub trait StackedBag: Sized {
+    /// Item stored in the bag
+    type Item;
+
+    /// Creates an empty bag
+    #[must_use = "Creating empty bag is usually a no-op"]
+    fn empty() -> Self;
+
+    /// Returns number of times the item was added to the bag
+    #[inline]
+    fn count(&self, item: impl Borrow<Self::Item>) -> usize {
+        self.count_ref(item.borrow())
+    }
+
+    /// Returns number of times the item was added to the bag
+    #[must_use = "Counting items does not change bag contents"]
+    fn count_ref(&self, item: &Self::Item) -> usize;
+
+    /// Checks if element is present in the bag at least once
+    #[inline]
+    #[must_use = "Checking for presence does not change bag contents"]
+    fn contains(&self, item: impl Borrow<Self::Item>) -> bool {
+        self.count_ref(item.borrow()) != 0
+    }
+
+    /// Total number of items in the bag, duplicates included
+    #[must_use = "Checking the length does not change bag contents"]
+    fn len(&self) -> usize;
+
+    /// Checks if the bag contains no items at all
+    #[inline]
+    #[must_use = "Checking the length does not change bag contents"]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of frames this bag consists of, forks included. See [`StackedSet::depth`]
+    #[must_use = "Checking the depth does not change bag contents"]
+    fn depth(&self) -> usize;
+
+    /// Intended to be the same type, but living for less time. See [`StackedSet::Shorten`]
+    type Shorten<'new>: StackedBag<Item = Self::Item> + 'new
+    where
+        Self: 'new;
+
+    /// Adds an item to the bag, returning a new instance now containing the item one more time
+    ///
+    /// Once returned instance is dropped, multiplicity of the item is back to what it was
+    #[must_use = "Provided value is only contained in a bag returned form this call. Despite requiring exclusive borrow, original bag is should not be expected to change. Check documentation for more details."]
+    fn extend(&mut self, new_item: Self::Item) -> Self::Shorten<'_>;
+
+    /// Same as [`StackedBag::extend`], but does not actually extend the bag. See [`StackedSet::fork`]
+    #[must_use = "Despite requiring exclusive borrow, original bag is should not be expected to change. Check documentation for more details."]
+    fn fork(&mut self) -> Self::Shorten<'_>;
+
+    /// Iterator type for the bag
+    type IntoIter<'i>: Iterator<Item = (&'i Self::Item, usize)> + 'i
+    where
+        Self: 'i;
+
+    /// Returns iterator over distinct items in the bag along with their multiplicities, no specific order guaranteed
+    fn iter(&self) -> Self::IntoIter<'_>;
+}
+
+pub use dyn_set::DynStackedSet;
+
+pub use cycle::{CycleError, CyclePath};
+
+pub use bounded::{Bounded, ConstLimits, LimitExceeded, Limits, RuntimeLimits};
+
+#[cfg(feature = "cons")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cons")))]
+pub use cons::ConsSet;
+
+/// [`ConsSet`] with no [`Fingerprint`], i.e. the plain alloc-free implementation
 #[cfg(feature = "cons")]
 #[cfg_attr(docsrs, doc(cfg(feature = "cons")))]
-pub use cons::ConsSet as StackCons;
+pub type StackCons<'tail, Item> = ConsSet<'tail, Item>;
+
+/// [`ConsSet`] with [`Bloom64`] fingerprint, rejecting most of the absent items without walking the list. Requires items to be [`Hash`]
+#[cfg(feature = "cons")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cons")))]
+pub type StackBloomCons<'tail, Item> = ConsSet<'tail, Item, Bloom64>;
+
+#[cfg(feature = "cons")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cons")))]
+pub use fingerprint::{Bloom64, Bloom128, Fingerprint, NoFingerprint};
+
+#[cfg(feature = "cons")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cons")))]
+pub use cons_bag::ConsBag as StackConsBag;
+
+#[cfg(feature = "cons")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cons")))]
+pub use cons_map::ConsMap as StackConsMap;
+
+#[cfg(feature = "array")]
+#[cfg_attr(docsrs, doc(cfg(feature = "array")))]
+pub use array::Array as InlineArray;
+
+#[cfg(feature = "slice")]
+#[cfg_attr(docsrs, doc(cfg(feature = "slice")))]
+pub use slice::Slice as SliceSet;
+
+#[cfg(feature = "bitset")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bitset")))]
+pub use bitset::BitIndex;
+
+#[cfg(feature = "bitset")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bitset")))]
+pub use bitset::BitArray as InlineBitArray;
+
+#[cfg(all(feature = "bitset", feature = "alloc-vec"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "bitset", feature = "alloc-vec"))))]
+pub use bitset::BitVec as AllocBitVec;
 
 #[cfg(feature = "alloc-vec")]
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc-vec")))]
@@ -95,9 +734,41 @@ pub use alloc_vec::Vec as AllocVec;
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc-tree")))]
 pub use alloc_tree::TreeSet as AllocTree;
 
+#[cfg(feature = "alloc-tree")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc-tree")))]
+pub use alloc_tree::TreeMap as AllocTreeMap;
+
+#[cfg(feature = "alloc-tree")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc-tree")))]
+pub use alloc_tree::TreeBag as AllocTreeBag;
+
+#[cfg(feature = "alloc-sorted")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc-sorted")))]
+pub use alloc_sorted::Sorted as AllocSortedVec;
+
 #[cfg(feature = "std-hash")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std-hash")))]
 pub use std_hash::Hash as StdHash;
 
+#[cfg(feature = "std-hash")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std-hash")))]
+pub use std_hash::HashWith as StdHashWith;
+
+#[cfg(feature = "std-hash")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std-hash")))]
+pub use std_hash::HashMap as StdHashMap;
+
+#[cfg(feature = "std-hash")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std-hash")))]
+pub use std_hash::HashBag as StdHashBag;
+
+#[cfg(feature = "alloc-hash")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc-hash")))]
+pub use alloc_hash::Hash as AllocHash;
+
+#[cfg(feature = "alloc-hash")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc-hash")))]
+pub use alloc_hash::HashWith as AllocHashWith;
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,56 @@
+use crate::collection::{Buffer, BufferCollection};
+
+/// Caller-provided buffer-based implementation, requiring no `alloc`. Items are pushed and popped within the buffer exactly like [`AllocVec`](crate::AllocVec) does
+///
+/// Create it with [`CollectionSet::new`](crate::collection::CollectionSet::new) over a [`SliceCollection`]:
+///
+/// ```rust
+/// # use stacked_set::{SliceSet, StackedSet, collection::SliceCollection};
+/// let mut buffer = [None; 16];
+/// let mut set = SliceSet::new(SliceCollection::with_buffer(&mut buffer));
+/// assert!(set.checked_extend(1).is_ok());
+/// ```
+///
+/// # Panics
+///
+/// [`StackedSet::empty`](crate::StackedSet::empty) creates a set with no buffer at all, i.e. zero capacity, so that every [`StackedSet`](crate::StackedSet) method extending it panics. Same goes for a set over a full buffer, so whenever the input is not known to fit, [`CollectionSet::checked_extend`](crate::collection::CollectionSet::checked_extend), [`CollectionSet::checked_try_extend`](crate::collection::CollectionSet::checked_try_extend) and [`CollectionSet::checked_extend_many`](crate::collection::CollectionSet::checked_extend_many) are the only way to extend the set without risking a panic
+pub type Slice<'l, 'b, Item> = crate::collection::CollectionSet<'l, SliceCollection<'b, Item>>;
+
+/// Caller-provided buffer, implementing [`SetCollection`](crate::collection::SetCollection)
+pub type SliceCollection<'b, Item> = BufferCollection<&'b mut [Option<Item>]>;
+
+impl<'b, Item> SliceCollection<'b, Item> {
+    /// Creates an empty collection over the buffer, dropping the items it holds. Capacity of the collection is the length of the buffer, so it's up to the caller to size it, e.g. with an array on the stack
+    #[inline]
+    #[must_use]
+    pub fn with_buffer(buffer: &'b mut [Option<Item>]) -> Self {
+        Self::with_slots(buffer)
+    }
+}
+
+impl<'b, Item> From<&'b mut [Option<Item>]> for SliceCollection<'b, Item> {
+    #[inline]
+    fn from(buffer: &'b mut [Option<Item>]) -> Self {
+        Self::with_buffer(buffer)
+    }
+}
+
+impl<Item> Buffer for &mut [Option<Item>] {
+    type Item = Item;
+
+    /// Empty buffer, as there's no way to allocate one. See [`Slice`]
+    #[inline]
+    fn new() -> Self {
+        &mut []
+    }
+
+    #[inline]
+    fn slots(&self) -> &[Option<Self::Item>] {
+        self
+    }
+
+    #[inline]
+    fn slots_mut(&mut self) -> &mut [Option<Self::Item>] {
+        self
+    }
+}
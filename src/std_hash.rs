@@ -1,13 +1,19 @@
-use core::hash::BuildHasher;
+use core::{borrow::Borrow, hash::BuildHasher};
 use std::collections::HashSet;
 
-use crate::collection::SetCollection;
+use crate::{
+    Lookup, Query, collection::SetCollection, collection_bag::BagCollection,
+    collection_map::MapCollection,
+};
 
 /// [`std::collections::HashSet`]-based implementation
 ///
 /// On my machine, time to check for existence is flat 10ns
 pub type Hash<'l, Item> = crate::collection::CollectionSet<'l, HashSet<Item>>;
 
+/// [`std::collections::HashSet`]-based implementation, hashing items with `S` instead of [`std::hash::RandomState`]
+pub type HashWith<'l, Item, S> = crate::collection::CollectionSet<'l, HashSet<Item, S>>;
+
 impl<Item: Clone + Eq + std::hash::Hash, S: BuildHasher + Default> SetCollection
     for HashSet<Item, S>
 {
@@ -15,6 +21,8 @@ impl<Item: Clone + Eq + std::hash::Hash, S: BuildHasher + Default> SetCollection
 
     type ExtendMemory = Item;
 
+    type ExtendManyMemory = std::vec::Vec<Item>;
+
     #[inline]
     fn new() -> Self {
         Self::with_hasher(S::default())
@@ -26,16 +34,62 @@ impl<Item: Clone + Eq + std::hash::Hash, S: BuildHasher + Default> SetCollection
         new_item
     }
 
+    #[inline]
+    fn try_extend(&mut self, new_item: Self::Item) -> Option<Self::ExtendMemory> {
+        // single lookup, as insertion reports item presence anyway
+        std::collections::HashSet::insert(self, new_item.clone()).then_some(new_item)
+    }
+
+    #[inline]
+    fn extend_many(
+        &mut self,
+        new_items: impl IntoIterator<Item = Self::Item>,
+    ) -> Self::ExtendManyMemory {
+        new_items
+            .into_iter()
+            .filter(|new_item| self.insert(new_item.clone()))
+            .collect()
+    }
+
     #[inline]
     fn contains_ref(&self, item: &Self::Item) -> bool {
         std::collections::HashSet::contains(self, item)
     }
 
+    #[inline]
+    fn len(&self) -> usize {
+        std::collections::HashSet::len(self)
+    }
+
     #[inline]
     fn remove(&mut self, present_item: Self::ExtendMemory) {
         std::collections::HashSet::remove(self, &present_item);
     }
 
+    #[inline]
+    fn remove_many(&mut self, present_items: Self::ExtendManyMemory) {
+        for present_item in present_items {
+            std::collections::HashSet::remove(self, &present_item);
+        }
+    }
+
+    #[inline]
+    fn extended_item<'a>(&'a self, memory: &'a Self::ExtendMemory) -> &'a Self::Item {
+        memory
+    }
+
+    type Added<'i>
+        = core::slice::Iter<'i, Item>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn extended_items<'a>(&'a self, memory: &'a Self::ExtendManyMemory) -> Self::Added<'a> {
+        memory.iter()
+    }
+
+    const LOOKUP: Lookup = Lookup::Hashed;
+
     type IntoIter<'i>
         = std::collections::hash_set::Iter<'i, Item>
     where
@@ -46,3 +100,128 @@ impl<Item: Clone + Eq + std::hash::Hash, S: BuildHasher + Default> SetCollection
         std::collections::HashSet::iter(self)
     }
 }
+
+impl<Item, S, Q> Query<HashSet<Item, S>> for Q
+where
+    Item: Clone + Eq + std::hash::Hash + Borrow<Q>,
+    S: BuildHasher + Default,
+    Q: ?Sized + Eq + std::hash::Hash,
+{
+    #[inline]
+    fn get_in<'a>(&self, collection: &'a HashSet<Item, S>) -> Option<&'a Item>
+    where
+        HashSet<Item, S>: 'a,
+    {
+        HashSet::get(collection, self)
+    }
+}
+
+/// [`std::collections::HashMap`]-based implementation of [`StackedMap`](crate::StackedMap)
+pub type HashMap<'l, Key, Value> =
+    crate::collection_map::CollectionMap<'l, std::collections::HashMap<Key, Value>>;
+
+impl<Key: Clone + Eq + std::hash::Hash, Value, S: BuildHasher + Default> MapCollection
+    for std::collections::HashMap<Key, Value, S>
+{
+    type Key = Key;
+
+    type Value = Value;
+
+    type InsertMemory = (Key, Option<Value>);
+
+    #[inline]
+    fn new() -> Self {
+        Self::with_hasher(S::default())
+    }
+
+    #[inline]
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Self::InsertMemory {
+        let shadowed = std::collections::HashMap::insert(self, key.clone(), value);
+        (key, shadowed)
+    }
+
+    #[inline]
+    fn get_ref(&self, key: &Self::Key) -> Option<&Self::Value> {
+        std::collections::HashMap::get(self, key)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        std::collections::HashMap::len(self)
+    }
+
+    #[inline]
+    fn remove(&mut self, (key, shadowed): Self::InsertMemory) {
+        match shadowed {
+            Some(value) => {
+                std::collections::HashMap::insert(self, key, value);
+            }
+            None => {
+                std::collections::HashMap::remove(self, &key);
+            }
+        }
+    }
+
+    type IntoIter<'i>
+        = std::collections::hash_map::Iter<'i, Key, Value>
+    where
+        Self: 'i;
+
+    #[inline]
+    fn iter(&self) -> Self::IntoIter<'_> {
+        std::collections::HashMap::iter(self)
+    }
+}
+
+/// [`std::collections::HashMap`]-based implementation of [`StackedBag`](crate::StackedBag)
+pub type HashBag<'l, Item> =
+    crate::collection_bag::CollectionBag<'l, std::collections::HashMap<Item, usize>>;
+
+impl<Item: Clone + Eq + std::hash::Hash, S: BuildHasher + Default> BagCollection
+    for std::collections::HashMap<Item, usize, S>
+{
+    type Item = Item;
+
+    type ExtendMemory = Item;
+
+    #[inline]
+    fn new() -> Self {
+        Self::with_hasher(S::default())
+    }
+
+    #[inline]
+    fn extend(&mut self, new_item: Self::Item) -> Self::ExtendMemory {
+        *self.entry(new_item.clone()).or_insert(0) += 1;
+        new_item
+    }
+
+    #[inline]
+    fn count_ref(&self, item: &Self::Item) -> usize {
+        std::collections::HashMap::get(self, item)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    #[inline]
+    fn remove(&mut self, present_item: Self::ExtendMemory) {
+        if let Some(count) = std::collections::HashMap::get_mut(self, &present_item) {
+            *count -= 1;
+            if *count == 0 {
+                std::collections::HashMap::remove(self, &present_item);
+            }
+        }
+    }
+
+    type IntoIter<'i>
+        = core::iter::Map<
+        std::collections::hash_map::Iter<'i, Item, usize>,
+        fn((&'i Item, &'i usize)) -> (&'i Item, usize),
+    >
+    where
+        Self: 'i;
+
+    #[inline]
+    fn iter(&self) -> Self::IntoIter<'_> {
+        std::collections::HashMap::iter(self).map(|(item, count)| (item, *count))
+    }
+}
@@ -1,6 +1,6 @@
 use core::any::TypeId;
 
-use crate::StackedSet;
+use crate::{Query, StackedSet};
 
 #[allow(unused)]
 type T1 = [(); 1];
@@ -133,11 +133,953 @@ macro_rules! test_impl {
 #[cfg(feature = "cons")]
 test_impl!(cons, crate::StackCons<'static, TypeId>);
 
+#[cfg(feature = "cons")]
+test_impl!(cons_bloom, crate::ConsSet<'static, TypeId, crate::Bloom128>);
+
 #[cfg(feature = "alloc-vec")]
 test_impl!(alloc_vec, crate::AllocVec<'static, TypeId>);
 
 #[cfg(feature = "alloc-tree")]
 test_impl!(alloc_tree, crate::AllocTree<'static, TypeId>);
 
+#[cfg(feature = "alloc-sorted")]
+test_impl!(alloc_sorted, crate::AllocSortedVec<'static, TypeId>);
+
 #[cfg(feature = "std-hash")]
 test_impl!(std_hash, crate::StdHash<'static, TypeId>);
+
+#[cfg(feature = "alloc-hash")]
+test_impl!(alloc_hash, crate::AllocHash<'static, TypeId>);
+
+/// User-provided hasher, to check that hash-based sets are not tied to the default one
+#[cfg(feature = "alloc-hash")]
+#[derive(Default)]
+struct XorHasher(u64);
+
+#[cfg(feature = "alloc-hash")]
+impl core::hash::Hasher for XorHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = self.0.rotate_left(8) ^ u64::from(*byte);
+        }
+    }
+}
+
+#[cfg(feature = "alloc-hash")]
+test_impl!(
+    alloc_hash_with,
+    crate::AllocHashWith<'static, TypeId, core::hash::BuildHasherDefault<XorHasher>>
+);
+
+#[inline]
+#[allow(unused)]
+fn test_contains_by<S: StackedSet<Item = &'static str>>()
+where
+    str: Query<S::Backend>,
+{
+    let mut stack = S::empty();
+    assert!(!stack.contains_by("a"));
+    {
+        let mut stack = stack.extend("a");
+        let stack = stack.extend_many(["b", "c"]);
+        assert!(stack.contains_by("a"));
+        assert!(stack.contains_by("b"));
+        assert!(stack.contains_by("c"));
+        assert!(!stack.contains_by("d"));
+    }
+    assert!(!stack.contains_by("a"));
+}
+
+#[allow(unused)]
+macro_rules! test_contains_by {
+    ($name:ident, $type:ty) => {
+        #[test]
+        fn $name() {
+            test_contains_by::<$type>();
+        }
+    };
+}
+
+#[cfg(feature = "cons")]
+test_contains_by!(cons_contains_by, crate::StackCons<'static, &'static str>);
+
+#[cfg(feature = "alloc-vec")]
+test_contains_by!(
+    alloc_vec_contains_by,
+    crate::AllocVec<'static, &'static str>
+);
+
+#[cfg(feature = "alloc-tree")]
+test_contains_by!(
+    alloc_tree_contains_by,
+    crate::AllocTree<'static, &'static str>
+);
+
+#[cfg(feature = "std-hash")]
+test_contains_by!(std_hash_contains_by, crate::StdHash<'static, &'static str>);
+
+#[inline]
+#[allow(unused)]
+fn test_get_string<S: StackedSet<Item = std::string::String>>()
+where
+    str: Query<S::Backend>,
+{
+    use std::string::ToString;
+
+    let mut stack = S::empty();
+    let mut stack = stack.extend("a".to_string());
+    let items = ["b".to_string(), "a".to_string()];
+    let stack = stack.extend_many(items);
+    assert_eq!(stack.get("a").map(std::string::String::as_str), Some("a"));
+    assert!(stack.contains_by("b"));
+    assert!(!stack.contains_by("c"));
+}
+
+#[allow(unused)]
+macro_rules! test_get_string {
+    ($name:ident, $type:ty) => {
+        #[test]
+        fn $name() {
+            test_get_string::<$type>();
+        }
+    };
+}
+
+#[cfg(feature = "cons")]
+test_get_string!(
+    cons_get_string,
+    crate::StackCons<'static, std::string::String>
+);
+
+#[cfg(feature = "array")]
+test_get_string!(
+    array_get_string,
+    crate::InlineArray<'static, std::string::String, 4>
+);
+
+#[cfg(feature = "alloc-vec")]
+test_get_string!(
+    alloc_vec_get_string,
+    crate::AllocVec<'static, std::string::String>
+);
+
+#[cfg(feature = "alloc-tree")]
+test_get_string!(
+    alloc_tree_get_string,
+    crate::AllocTree<'static, std::string::String>
+);
+
+#[cfg(feature = "alloc-sorted")]
+test_get_string!(
+    alloc_sorted_get_string,
+    crate::AllocSortedVec<'static, std::string::String>
+);
+
+#[cfg(feature = "std-hash")]
+test_get_string!(
+    std_hash_get_string,
+    crate::StdHash<'static, std::string::String>
+);
+
+#[cfg(feature = "alloc-hash")]
+test_get_string!(
+    alloc_hash_get_string,
+    crate::AllocHash<'static, std::string::String>
+);
+
+#[cfg(feature = "cons")]
+test_get_string!(
+    bounded_get_string,
+    crate::Bounded<crate::StackCons<'static, std::string::String>, crate::RuntimeLimits>
+);
+
+/// Lookup key, that can only be compared for equality
+#[derive(Debug, PartialEq)]
+struct Name(&'static str);
+
+/// Item, that is looked up by its [`Name`]
+#[derive(Debug, PartialEq)]
+struct Named(Name, u32);
+
+impl core::borrow::Borrow<Name> for Named {
+    fn borrow(&self) -> &Name {
+        &self.0
+    }
+}
+
+#[inline]
+#[allow(unused)]
+fn test_get_eq<S: StackedSet<Item = Named>>()
+where
+    Name: Query<S::Backend>,
+{
+    let mut stack = S::empty();
+    let mut stack = stack.extend(Named(Name("a"), 1));
+    let stack = stack.extend(Named(Name("b"), 2));
+    assert_eq!(stack.get(&Name("a")).map(|named| named.1), Some(1));
+    assert!(stack.contains_by(&Name("b")));
+    assert!(!stack.contains_by(&Name("c")));
+}
+
+#[cfg(feature = "cons")]
+#[test]
+fn cons_get_eq() {
+    test_get_eq::<crate::StackCons<'static, Named>>();
+}
+
+#[cfg(feature = "array")]
+#[test]
+fn array_get_eq() {
+    test_get_eq::<crate::InlineArray<'static, Named, 2>>();
+}
+
+#[cfg(feature = "alloc-vec")]
+#[test]
+fn alloc_vec_get_eq() {
+    test_get_eq::<crate::AllocVec<'static, Named>>();
+}
+
+/// Item with a payload, that does not take part in comparisons
+#[derive(Debug, Clone, Copy)]
+struct Entered {
+    id: u32,
+    at: u32,
+}
+
+impl PartialEq for Entered {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Entered {}
+
+impl PartialOrd for Entered {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entered {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl core::hash::Hash for Entered {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl core::borrow::Borrow<u32> for Entered {
+    fn borrow(&self) -> &u32 {
+        &self.id
+    }
+}
+
+#[inline]
+#[allow(unused)]
+fn test_get<S: StackedSet<Item = Entered>>()
+where
+    u32: Query<S::Backend>,
+    Entered: Query<S::Backend>,
+{
+    let mut stack = S::empty();
+    assert!(stack.get(&1).is_none());
+    {
+        let mut stack = stack.extend(Entered { id: 1, at: 10 });
+        let mut stack = stack.extend(Entered { id: 2, at: 20 });
+        let mut stack = stack.extend(Entered { id: 1, at: 30 });
+        let stack = stack.extend_many([Entered { id: 3, at: 40 }, Entered { id: 2, at: 50 }]);
+        assert_eq!(stack.get(&1).map(|e| e.at), Some(10));
+        assert_eq!(stack.get(&2).map(|e| e.at), Some(20));
+        assert_eq!(stack.get(&3).map(|e| e.at), Some(40));
+        assert_eq!(stack.get(&Entered { id: 3, at: 0 }).map(|e| e.at), Some(40));
+        assert!(stack.get(&4).is_none());
+    }
+    assert!(stack.get(&1).is_none());
+}
+
+#[allow(unused)]
+macro_rules! test_get {
+    ($name:ident, $type:ty) => {
+        #[test]
+        fn $name() {
+            test_get::<$type>();
+        }
+    };
+}
+
+#[cfg(feature = "cons")]
+test_get!(cons_get, crate::StackCons<'static, Entered>);
+
+#[cfg(feature = "alloc-vec")]
+test_get!(alloc_vec_get, crate::AllocVec<'static, Entered>);
+
+#[cfg(feature = "alloc-tree")]
+test_get!(alloc_tree_get, crate::AllocTree<'static, Entered>);
+
+#[cfg(feature = "alloc-sorted")]
+test_get!(alloc_sorted_get, crate::AllocSortedVec<'static, Entered>);
+
+#[cfg(feature = "std-hash")]
+test_get!(std_hash_get, crate::StdHash<'static, Entered>);
+
+#[cfg(feature = "alloc-hash")]
+test_get!(alloc_hash_get, crate::AllocHash<'static, Entered>);
+
+#[cfg(feature = "alloc-hash")]
+#[test]
+fn alloc_hash_equivalent() {
+    /// Lookup key, that is not a borrowed form of [`Entered`]
+    #[derive(Hash)]
+    struct Id(u32);
+
+    impl hashbrown::Equivalent<Entered> for Id {
+        fn equivalent(&self, key: &Entered) -> bool {
+            self.0 == key.id
+        }
+    }
+
+    let mut set = crate::AllocHash::<'static, Entered>::empty();
+    let set = set.extend(Entered { id: 1, at: 10 });
+    assert_eq!(set.get(&Id(1)).map(|e| e.at), Some(10));
+    assert!(set.contains_by(&Id(1)));
+    assert!(!set.contains_by(&Id(2)));
+    // borrowed forms are equivalent too
+    assert_eq!(set.get(&1).map(|e| e.at), Some(10));
+}
+
+#[inline]
+#[allow(unused)]
+fn test_algebra<A: StackedSet<Item = u32>, B: StackedSet<Item = u32>>() {
+    let mut a = A::empty();
+    let mut b = B::empty();
+    let a = a.extend_many([1, 2, 3]);
+    let mut b = b.extend_many([5, 4, 3, 2, 1]);
+    assert!(a.is_subset_of(&b));
+    assert!(b.is_superset_of(&a));
+    assert!(!b.is_subset_of(&a));
+    assert!(!a.is_disjoint(&b));
+    assert!(!b.is_disjoint(&a));
+    assert!(a.contains_all([1, 2, 3]));
+    assert!(!a.contains_all([1, 2, 3, 4]));
+    assert!(a.contains_any([0, 4, 3]));
+    assert!(!a.contains_any(&[0, 4, 5]));
+    assert_eq!(b.intersection(&a).count(), 3);
+    assert!(b.intersection(&a).all(|item| (1..=3).contains(item)));
+    {
+        let b = b.fork();
+        assert!(b.is_superset_of(&a));
+    }
+
+    let mut c = A::empty();
+    let c = c.extend_many([6, 7, 0]);
+    assert!(c.is_disjoint(&b));
+    assert!(b.is_disjoint(&c));
+    assert!(!c.is_subset_of(&b));
+    assert_eq!(b.intersection(&c).count(), 0);
+}
+
+#[allow(unused)]
+macro_rules! test_algebra {
+    ($name:ident, $a:ty, $b:ty) => {
+        #[test]
+        fn $name() {
+            test_algebra::<$a, $b>();
+        }
+    };
+}
+
+#[cfg(feature = "cons")]
+test_algebra!(
+    cons_cons_algebra,
+    crate::StackCons<'static, u32>,
+    crate::StackCons<'static, u32>
+);
+
+#[cfg(all(feature = "cons", feature = "alloc-tree"))]
+test_algebra!(
+    cons_alloc_tree_algebra,
+    crate::StackCons<'static, u32>,
+    crate::AllocTree<'static, u32>
+);
+
+#[cfg(all(feature = "alloc-tree", feature = "std-hash"))]
+test_algebra!(
+    alloc_tree_std_hash_algebra,
+    crate::AllocTree<'static, u32>,
+    crate::StdHash<'static, u32>
+);
+
+#[cfg(feature = "alloc-tree")]
+test_algebra!(
+    alloc_tree_alloc_tree_algebra,
+    crate::AllocTree<'static, u32>,
+    crate::AllocTree<'static, u32>
+);
+
+#[cfg(all(feature = "alloc-sorted", feature = "alloc-tree"))]
+test_algebra!(
+    alloc_sorted_alloc_tree_algebra,
+    crate::AllocSortedVec<'static, u32>,
+    crate::AllocTree<'static, u32>
+);
+
+#[cfg(all(feature = "std-hash", feature = "alloc-vec"))]
+test_algebra!(
+    std_hash_alloc_vec_algebra,
+    crate::StdHash<'static, u32>,
+    crate::AllocVec<'static, u32>
+);
+
+#[cfg(feature = "collection")]
+#[allow(unused)]
+fn test_commit<C: crate::collection::SetCollection<Item = u32>>() {
+    fn add_1_and_2<C: crate::collection::SetCollection<Item = u32>>(
+        set: &mut crate::collection::CollectionSet<'_, C>,
+    ) {
+        set.extend(1).commit();
+        set.extend_many([2, 1]).commit();
+    }
+
+    let mut set = crate::collection::CollectionSet::<C>::empty();
+    {
+        let mut with_3 = set.extend(3);
+        {
+            let mut with_4 = with_3.extend(4);
+            add_1_and_2(&mut with_4);
+            assert_eq!(with_4.len(), 4);
+            assert_eq!(with_4.depth(), 2);
+            // committed items don't belong to any frame
+            let mut path = with_4.iter_path();
+            assert_eq!(path.next(), Some(&3));
+            assert_eq!(path.next(), Some(&4));
+            assert_eq!(path.next(), None);
+            assert_eq!(with_4.position(|item| *item == 4), Some(1));
+            drop(path);
+            let with_5 = with_4.extend(5);
+            assert_eq!(with_5.iter_path().len(), 3);
+            assert!(with_5.iter_path().eq(&[3, 4, 5]));
+        }
+        // committed items outlive the frame they were committed into
+        assert!(with_3.contains_all([1, 2, 3]));
+        assert!(!with_3.contains(4));
+        assert_eq!(with_3.top(), Some(&3));
+        assert!(with_3.extend(2).is_fork());
+    }
+    assert!(set.contains_all([1, 2]));
+    assert!(!set.contains(3));
+    set.fork().commit();
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.depth(), 0);
+
+    let mut collection = C::new();
+    let _ = collection.extend(7);
+    let mut set = crate::collection::CollectionSet::new(collection);
+    let mut with_8 = set.extend(8);
+    let with_10 = with_8.extend_many([9, 7, 10]);
+    // items present before the root frame don't belong to any frame either
+    assert_eq!(with_10.iter_path().len(), 3);
+    assert!(with_10.iter_path().eq(&[8, 9, 10]));
+}
+
+#[allow(unused)]
+macro_rules! test_commit {
+    ($name:ident, $type:ty) => {
+        #[test]
+        fn $name() {
+            test_commit::<$type>();
+        }
+    };
+}
+
+#[cfg(feature = "alloc-vec")]
+test_commit!(alloc_vec_commit, alloc::vec::Vec<u32>);
+
+#[cfg(feature = "alloc-tree")]
+test_commit!(alloc_tree_commit, alloc::collections::BTreeSet<u32>);
+
+#[cfg(feature = "alloc-sorted")]
+test_commit!(alloc_sorted_commit, crate::collection::SortedVec<u32>);
+
+#[cfg(feature = "std-hash")]
+test_commit!(std_hash_commit, std::collections::HashSet<u32>);
+
+#[cfg(feature = "alloc-hash")]
+test_commit!(alloc_hash_commit, hashbrown::HashSet<u32>);
+
+#[cfg(all(feature = "collection", feature = "alloc"))]
+#[allow(unused)]
+fn test_journal<C: crate::collection::SetCollection<Item = u32>>() {
+    use crate::collection::StaleCheckpoint;
+
+    let mut set = crate::collection::CollectionSet::<C>::empty();
+    let mut with_1 = set.extend(1);
+    {
+        let mut journal = with_1.journal();
+        assert_eq!(journal.depth(), 2);
+        let empty = journal.checkpoint();
+        assert!(journal.insert(2));
+        assert!(!journal.insert(1));
+        assert!(journal.insert(3));
+        let with_3 = journal.checkpoint();
+        assert!(journal.insert(4));
+        assert!(journal.insert(5));
+        assert!(journal.contains_all([1, 2, 3, 4, 5]));
+        assert!(journal.added_since(with_3).unwrap().eq(&[4, 5]));
+        assert!(journal.added_since(empty).unwrap().eq(&[2, 3, 4, 5]));
+
+        assert_eq!(journal.rollback_to(with_3), Ok(()));
+        assert!(!journal.contains(4) && !journal.contains(5));
+        assert!(journal.insert(6));
+        // rolling back to the same checkpoint twice is fine
+        assert_eq!(journal.rollback_to(with_3), Ok(()));
+        assert!(journal.insert(7));
+        let with_7 = journal.checkpoint();
+
+        assert_eq!(journal.rollback_to(empty), Ok(()));
+        assert_eq!(journal.len(), 1);
+        // same length as before, but different items
+        assert!(journal.insert(8));
+        assert!(journal.insert(9));
+        assert!(journal.insert(10));
+        assert_eq!(journal.rollback_to(with_3), Err(StaleCheckpoint));
+        assert!(journal.added_since(with_7).is_err());
+        assert!(journal.contains_all([1, 8, 9, 10]));
+    }
+    assert_eq!(with_1.len(), 1);
+    assert_eq!(with_1.depth(), 1);
+}
+
+#[allow(unused)]
+macro_rules! test_journal {
+    ($name:ident, $type:ty) => {
+        #[test]
+        fn $name() {
+            test_journal::<$type>();
+        }
+    };
+}
+
+#[cfg(feature = "alloc-vec")]
+test_journal!(alloc_vec_journal, alloc::vec::Vec<u32>);
+
+#[cfg(feature = "alloc-tree")]
+test_journal!(alloc_tree_journal, alloc::collections::BTreeSet<u32>);
+
+#[cfg(feature = "alloc-sorted")]
+test_journal!(alloc_sorted_journal, crate::collection::SortedVec<u32>);
+
+#[cfg(feature = "std-hash")]
+test_journal!(std_hash_journal, std::collections::HashSet<u32>);
+
+#[cfg(feature = "alloc-hash")]
+test_journal!(alloc_hash_journal, hashbrown::HashSet<u32>);
+
+#[cfg(feature = "array")]
+test_commit!(array_commit, crate::collection::ArrayCollection<u32, 8>);
+
+#[cfg(all(feature = "array", feature = "alloc"))]
+test_journal!(array_journal, crate::collection::ArrayCollection<u32, 8>);
+
+#[cfg(feature = "array")]
+#[test]
+fn array_capacity() {
+    use crate::collection::CapacityExceeded;
+
+    const FULL: CapacityExceeded = CapacityExceeded { capacity: 3 };
+
+    let mut set = crate::InlineArray::<'static, u32, 3>::empty();
+    let mut with_1 = set.checked_extend(1).unwrap();
+    assert_eq!(with_1.checked_extend_many([2, 3, 4]).err(), Some(FULL));
+    assert_eq!(with_1.len(), 1);
+    let mut full = with_1.checked_extend_many([2, 1, 3, 2]).unwrap();
+    assert!(full.iter().eq(&[1, 2, 3]));
+    assert_eq!(full.checked_extend(4).err(), Some(FULL));
+    // already present items don't need any space
+    assert!(full.checked_try_extend(3).unwrap().is_already_present());
+    assert_eq!(full.fork().len(), 3);
+}
+
+#[cfg(feature = "array")]
+#[test]
+fn array_checked_single_lookup() {
+    /// Item counting comparisons made against it
+    #[derive(Debug)]
+    struct Compared<'c>(u32, &'c core::cell::Cell<usize>);
+
+    impl PartialEq for Compared<'_> {
+        fn eq(&self, other: &Self) -> bool {
+            self.1.set(self.1.get() + 1);
+            self.0 == other.0
+        }
+    }
+
+    let comparisons = core::cell::Cell::new(0);
+    let mut set = crate::InlineArray::<'_, Compared<'_>, 2>::empty();
+    let mut with_1 = set.extend(Compared(1, &comparisons));
+    comparisons.set(0);
+    let mut with_2 = with_1.checked_extend(Compared(2, &comparisons)).unwrap();
+    assert_eq!(comparisons.get(), 1);
+    comparisons.set(0);
+    // once full, the item is still looked up only once
+    assert!(
+        with_2
+            .checked_try_extend(Compared(1, &comparisons))
+            .unwrap()
+            .is_already_present()
+    );
+    assert!(with_2.checked_extend(Compared(3, &comparisons)).is_err());
+    assert_eq!(comparisons.get(), 1 + 2);
+}
+
+/// Item counting its drops, implementing neither `Default`, nor `Clone`
+#[cfg(any(feature = "array", feature = "alloc-sorted"))]
+#[derive(Debug)]
+struct Dropped<'c>(u32, &'c core::cell::Cell<usize>);
+
+#[cfg(any(feature = "array", feature = "alloc-sorted"))]
+impl PartialEq for Dropped<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+#[cfg(feature = "alloc-sorted")]
+impl Eq for Dropped<'_> {}
+
+#[cfg(feature = "alloc-sorted")]
+impl PartialOrd for Dropped<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "alloc-sorted")]
+impl Ord for Dropped<'_> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+#[cfg(any(feature = "array", feature = "alloc-sorted"))]
+impl Drop for Dropped<'_> {
+    fn drop(&mut self) {
+        self.1.set(self.1.get() + 1);
+    }
+}
+
+#[cfg(feature = "array")]
+#[test]
+fn array_drops_removed() {
+    let drops = core::cell::Cell::new(0);
+    let mut set = crate::InlineArray::<'_, Dropped<'_>, 3>::empty();
+    {
+        let mut with_1 = set.extend(Dropped(1, &drops));
+        {
+            let mut with_2 = with_1.extend(Dropped(2, &drops));
+            with_2.extend(Dropped(3, &drops)).commit();
+        }
+        // item is dropped once its frame is, rather than once its slot is reused
+        assert_eq!(drops.get(), 1);
+        assert!(with_1.iter().map(|item| item.0).eq([1, 3]));
+    }
+    assert_eq!(drops.get(), 2);
+    assert_eq!(set.len(), 1);
+}
+
+#[cfg(feature = "alloc-sorted")]
+#[test]
+fn alloc_sorted_drops_removed() {
+    use crate::collection::SetCollection;
+
+    let drops = core::cell::Cell::new(0);
+    let mut set = crate::AllocSortedVec::<'_, Dropped<'_>>::empty();
+    {
+        let mut with_3 = set.extend(Dropped(3, &drops));
+        {
+            let mut with_1 = with_3.extend(Dropped(1, &drops));
+            // committed item lands in front of the ones added by frames, but is not on the path
+            with_1.extend(Dropped(0, &drops)).commit();
+            assert!(with_1.iter().map(|item| item.0).eq([0, 1, 3]));
+            assert!(with_1.iter_path().map(|item| item.0).eq([3, 1]));
+        }
+        assert_eq!(drops.get(), 1);
+        assert!(with_3.iter().map(|item| item.0).eq([0, 3]));
+    }
+    assert_eq!(drops.get(), 2);
+    assert!(set.iter().map(|item| item.0).eq([0]));
+
+    let mut collection = crate::collection::SortedVec::new();
+    let _ = collection.extend(Dropped(3, &drops));
+    let memory = collection.extend_many([Dropped(4, &drops), Dropped(1, &drops)]);
+    assert!(
+        collection
+            .extended_items(&memory)
+            .map(|item| item.0)
+            .eq([4, 1])
+    );
+    assert!(collection.added().unwrap().map(|item| item.0).eq([3, 4, 1]));
+    collection.remove_many(memory);
+    assert_eq!(drops.get(), 4);
+    assert!(collection.as_slice().iter().map(|item| item.0).eq([3]));
+}
+
+#[cfg(feature = "array")]
+#[test]
+#[should_panic = "Buffer-based set capacity of 2 exceeded"]
+fn array_overflow() {
+    let mut set = crate::InlineArray::<'static, u32, 2>::empty();
+    let _ = set.extend_many([1, 2, 3]);
+}
+
+#[cfg(feature = "slice")]
+#[test]
+fn slice_buffer() {
+    use crate::collection::{CapacityExceeded, SliceCollection};
+
+    let mut buffer = [None; 4];
+    let mut set = crate::SliceSet::new(SliceCollection::with_buffer(&mut buffer));
+    {
+        let mut with_1 = set.extend(1);
+        {
+            let mut with_2 = with_1.extend(2);
+            with_2.extend(3).commit();
+            let with_4_5 = with_2.checked_extend_many([4, 5, 4]);
+            assert_eq!(with_4_5.err(), Some(CapacityExceeded { capacity: 4 }));
+            let mut with_4 = with_2.extend_many([4, 3]);
+            assert!(with_4.iter().eq(&[1, 2, 3, 4]));
+            assert!(
+                with_4
+                    .frames()
+                    .map(|(_, item)| item)
+                    .eq([Some(&4), Some(&2), Some(&1)])
+            );
+            assert!(with_4.checked_extend(5).is_err());
+            assert!(with_4.checked_extend(1).is_ok());
+        }
+        // committed item stays, while the ones around it are removed
+        assert!(with_1.iter().eq(&[1, 3]));
+    }
+    assert!(set.iter().eq(&[3]));
+    assert_eq!(set.depth(), 0);
+
+    let mut unbuffered = crate::SliceSet::<'static, 'static, u32>::empty();
+    assert!(unbuffered.checked_extend(1).is_err());
+}
+
+#[cfg(feature = "bitset")]
+test_commit!(
+    bitset_commit,
+    crate::collection::BitCollection<crate::collection::ArrayCollection<u32, 8>, [u64; 1]>
+);
+
+#[cfg(all(feature = "bitset", feature = "alloc-vec"))]
+test_journal!(
+    bitset_journal,
+    crate::collection::BitCollection<alloc::vec::Vec<u32>, alloc::vec::Vec<u64>>
+);
+
+#[cfg(all(feature = "bitset", feature = "cons"))]
+test_algebra!(
+    bitset_cons_algebra,
+    crate::InlineBitArray<'static, u32, 1, 8>,
+    crate::StackCons<'static, u32>
+);
+
+#[cfg(feature = "bitset")]
+crate::bit_index_enum! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq)]
+    enum Token {
+        #[default]
+        Open,
+        Close = 64,
+        Comma,
+    }
+}
+
+#[cfg(feature = "bitset")]
+#[test]
+fn bitset_frames() {
+    use crate::BitIndex;
+
+    let mut set = crate::InlineBitArray::<'static, Token, 2, 3>::empty();
+    let mut with_close = set.extend(Token::Close);
+    {
+        let with_all = with_close.extend_many([Token::Open, Token::Comma, Token::Open]);
+        assert_eq!(with_all.len(), 3);
+        assert_eq!(with_all.bits(), &[1, 0b11]);
+        assert!(with_all.frames().map(|(_, item)| item).eq([
+            Some(&Token::Comma),
+            Some(&Token::Open),
+            Some(&Token::Close)
+        ]));
+    }
+    // only the bits of the dropped frame are cleared
+    assert_eq!(with_close.bits(), &[0, 0b1]);
+    assert!(with_close.contains(Token::Close));
+    assert!(!with_close.contains(Token::Comma));
+    assert_eq!(with_close.get(&Token::Close), Some(&Token::Close));
+    assert_eq!(with_close.get(&Token::Comma), None);
+    assert_eq!(Token::Comma.bit_index(), 65);
+}
+
+#[cfg(feature = "bitset")]
+#[test]
+fn bitset_out_of_range() {
+    let mut set = crate::InlineBitArray::<'static, u32, 1, 4>::empty();
+    let mut with_1 = set.extend(1);
+    let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let _ = with_1.extend_many([64]);
+    }));
+    assert!(panicked.is_err());
+    // out-of-range item is not stored without its bit
+    assert_eq!(with_1.len(), 1);
+    assert_eq!(with_1.bits(), &[0b10]);
+    let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let _ = with_1.extend(64);
+    }));
+    assert!(panicked.is_err());
+    assert_eq!(with_1.len(), 1);
+    assert!(with_1.iter().eq(&[1]));
+}
+
+#[cfg(feature = "bitset")]
+#[test]
+fn bitset_overflow() {
+    let mut set = crate::InlineBitArray::<'static, u32, 1, 2>::empty();
+    let mut with_1 = set.extend(1);
+    let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let _ = with_1.extend_many([1, 2, 2, 3]);
+    }));
+    assert!(panicked.is_err());
+    // preceding item stays stored along with its bit, the overflowing one has neither
+    assert!(with_1.iter().eq(&[1, 2]));
+    assert_eq!(with_1.bits(), &[0b110]);
+}
+
+#[cfg(all(feature = "bitset", feature = "alloc-vec"))]
+#[test]
+fn bitset_grows() {
+    let mut set = crate::AllocBitVec::<'static, u16>::empty();
+    let mut with_big = set.extend(4000);
+    assert_eq!(with_big.bits().len(), 63);
+    let with_small = with_big.extend(3);
+    assert!(with_small.contains_all([3, 4000]));
+    assert!(!with_small.contains(4001) && !with_small.contains(u16::MAX));
+}
+
+#[cfg(feature = "cons")]
+#[test]
+fn bloom_fingerprint() {
+    use crate::{Bloom64, Fingerprint};
+
+    let of = |item: u32| <Bloom64 as Fingerprint<u32>>::of(&item);
+    let union = <Bloom64 as Fingerprint<u32>>::union;
+    let may_contain = <Bloom64 as Fingerprint<u32>>::may_contain;
+    let empty = <Bloom64 as Fingerprint<u32>>::EMPTY;
+
+    assert_eq!(of(1), of(1));
+    assert!(may_contain(union(of(1), of(2)), of(1)));
+    assert!(may_contain(union(of(1), of(2)), of(2)));
+    assert!(may_contain(union(empty, of(1)), of(1)));
+    assert!(!may_contain(empty, of(1)));
+}
+
+#[cfg(feature = "cons")]
+#[test]
+fn cons_ancestors() {
+    fn check(set: &crate::StackCons<'_, u32>, depth: usize) {
+        assert_eq!(set.depth(), depth);
+        for n in 0..=depth {
+            let ancestor = set.nth_ancestor(n).unwrap();
+            assert_eq!(ancestor.depth(), depth - n);
+            // ancestors are the same frames `parent` walks through
+            let mut parent = set;
+            for _ in 0..n {
+                parent = parent.parent().unwrap();
+            }
+            assert!(core::ptr::eq(ancestor, parent));
+        }
+        assert!(set.nth_ancestor(depth + 1).is_none());
+    }
+
+    fn recurse(set: &mut crate::StackCons<'_, u32>, depth: usize) {
+        check(set, depth);
+        if depth == 40 {
+            assert_eq!(set.len(), 10);
+            assert!(set.iter().copied().eq((0..10).rev()));
+            assert!(set.contains_all(0..10) && !set.contains(10));
+            assert_eq!(set.get(&3), Some(&3));
+            return;
+        }
+        // mostly forks and duplicates, that lookups should skip over
+        let item = u32::try_from(depth / 4).unwrap();
+        if depth % 4 == 1 {
+            recurse(&mut set.fork(), depth + 1);
+        } else {
+            recurse(&mut set.extend(item), depth + 1);
+        }
+    }
+
+    recurse(&mut crate::StackCons::empty(), 0);
+}
+
+#[cfg(feature = "cons")]
+#[test]
+fn cons_extend_many_no_clone() {
+    /// Item that can't be cloned into a collection
+    #[derive(Debug, PartialEq)]
+    struct Token(u32);
+
+    let tokens = [Token(1), Token(2), Token(1)];
+    let mut set = crate::StackCons::empty();
+    let mut set = set.extend_many(&tokens);
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(Token(2)));
+    // owned items are kept by the frame itself
+    let set = StackedSet::extend_many(&mut set, [Token(3), Token(2), Token(3)]);
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(Token(3)));
+}
+
+#[cfg(feature = "cons")]
+#[test]
+fn cons_extend_many_past_mask() {
+    // only the first 64 items of a slice are remembered to be new, the rest are looked up
+    let items: [u32; 100] = core::array::from_fn(|index| (index % 80) as u32);
+    let mut set = crate::StackCons::empty();
+    let mut with_70 = set.extend(70);
+    for (new_items, added) in [(&items[..], 79), (&items[..70], 70), (&items[70..80], 9)] {
+        let with_items = with_70.extend_many(new_items);
+        assert_eq!(with_items.len(), added + 1);
+        assert_eq!(with_items.iter().count(), added + 1);
+        assert_eq!(
+            with_items.frames().filter_map(|(_, item)| item).count(),
+            added + 1
+        );
+        // repeated items are reported by the frame that actually added them
+        assert!(with_items.iter().filter(|&&item| item == 70).count() == 1);
+        let mut path = with_items.iter_path();
+        assert_eq!(path.len(), added + 1);
+        assert_eq!(path.next(), Some(&70));
+        assert!(
+            path.copied()
+                .eq((0..80).filter(|item| *item != 70 && new_items.contains(item)))
+        );
+        assert_eq!(
+            with_items.get(&70).map(core::ptr::from_ref),
+            with_items
+                .parent()
+                .unwrap()
+                .get(&70)
+                .map(core::ptr::from_ref)
+        );
+    }
+}
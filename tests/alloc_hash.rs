@@ -0,0 +1,6 @@
+#![allow(missing_docs)]
+#![cfg(feature = "alloc-hash")]
+
+mod common;
+
+tests!(stacked_set::AllocHash::<'static, i32>);
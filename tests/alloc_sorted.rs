@@ -0,0 +1,6 @@
+#![allow(missing_docs)]
+#![cfg(feature = "alloc-sorted")]
+
+mod common;
+
+tests!(stacked_set::AllocSortedVec::<'static, i32>);
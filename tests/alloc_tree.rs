@@ -4,3 +4,5 @@
 mod common;
 
 tests!(stacked_set::AllocTree::<'static, i32>);
+map_tests!(stacked_set::AllocTreeMap::<'static, i32, &'static str>);
+bag_tests!(stacked_set::AllocTreeBag::<'static, i32>);
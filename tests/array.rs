@@ -0,0 +1,6 @@
+#![allow(missing_docs)]
+#![cfg(feature = "array")]
+
+mod common;
+
+tests!(stacked_set::InlineArray::<'static, i32, 64>);
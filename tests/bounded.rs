@@ -0,0 +1,38 @@
+#![allow(missing_docs)]
+#![cfg(feature = "cons")]
+
+mod common;
+
+tests!(stacked_set::Bounded::<stacked_set::StackCons<'static, i32>, stacked_set::RuntimeLimits>);
+
+#[test]
+#[should_panic = "stacked set length limit of 2 exceeded"]
+fn extend_past_len_limit() {
+    use stacked_set::{Bounded, StackedSet};
+
+    let mut set = Bounded::new(
+        stacked_set::StackCons::<'static, i32>::empty(),
+        stacked_set::RuntimeLimits {
+            max_depth: 10,
+            max_len: 2,
+        },
+    );
+    let mut with_1 = set.extend(1);
+    let _ = with_1.extend_many([1, 2, 3]);
+}
+
+#[test]
+#[should_panic = "stacked set depth limit of 1 exceeded"]
+fn fork_past_depth_limit() {
+    use stacked_set::{Bounded, StackedSet};
+
+    let mut set = Bounded::new(
+        stacked_set::StackCons::<'static, i32>::empty(),
+        stacked_set::RuntimeLimits {
+            max_depth: 1,
+            max_len: 10,
+        },
+    );
+    let mut with_1 = set.extend(1);
+    let _ = with_1.fork();
+}
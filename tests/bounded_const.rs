@@ -0,0 +1,12 @@
+#![allow(missing_docs)]
+#![cfg(feature = "cons")]
+
+mod common;
+
+// limits are enforced, yet generous enough for the whole suite
+tests!(
+    stacked_set::Bounded::<
+        stacked_set::StackCons<'static, i32>,
+        stacked_set::ConstLimits<1000, 1000>,
+    >
+);
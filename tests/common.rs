@@ -1,15 +1,19 @@
 #![allow(
     missing_debug_implementations,
     missing_docs,
-    clippy::missing_panics_doc
+    clippy::missing_panics_doc,
+    dead_code // not every implementation is tested against every function here
 )]
 
-use stacked_set::StackedSet;
+use stacked_set::{
+    Bounded, ConstLimits, CycleError, DynStackedSet, Extended, LimitExceeded, RuntimeLimits,
+    StackedBag, StackedMap, StackedSet,
+};
 
 #[macro_export]
 macro_rules! tests {
     ($tp:ty) => {
-        tests!{@ $tp: create_empty, add_single, odd_to_100}
+        tests!{@ $tp: create_empty, add_single, odd_to_100, len_and_depth, extend_many, try_extend, extend_unique, frames, iter_path, with_extended, dyn_set, predicates, enter, bounded}
     };
     (@ $tp:ty: $($name:ident),+) => {
         $(#[allow(missing_debug_implementations, missing_docs)]
@@ -20,6 +24,20 @@ macro_rules! tests {
     }
 }
 
+#[macro_export]
+macro_rules! map_tests {
+    ($tp:ty) => {
+        tests! {@ $tp: map_create_empty, map_shadowing, map_fork}
+    };
+}
+
+#[macro_export]
+macro_rules! bag_tests {
+    ($tp:ty) => {
+        tests! {@ $tp: bag_counts, bag_odd_to_100}
+    };
+}
+
 pub fn create_empty<S: StackedSet<Item = i32>>() {
     let s = S::empty();
     assert!(!s.contains(1));
@@ -74,3 +92,473 @@ pub fn odd_to_100<S: StackedSet<Item = i32>>() {
         (1..=100).filter(|i| *i & 1 == 1).collect::<Vec<i32>>()
     );
 }
+
+pub fn len_and_depth<S: StackedSet<Item = i32>>() {
+    let mut s = S::empty();
+    assert_eq!(s.len(), 0);
+    assert_eq!(s.depth(), 0);
+    assert!(s.is_empty());
+
+    {
+        let mut with_1 = s.extend(1);
+        assert_eq!(with_1.len(), 1);
+        assert_eq!(with_1.depth(), 1);
+        assert!(!with_1.is_empty());
+
+        let mut forked = with_1.fork();
+        assert_eq!(forked.len(), 1);
+        assert_eq!(forked.depth(), 2);
+
+        let mut with_1_again = forked.extend(1);
+        assert_eq!(with_1_again.len(), 1);
+        assert_eq!(with_1_again.depth(), 3);
+
+        let with_2 = with_1_again.extend(2);
+        assert_eq!(with_2.len(), 2);
+        assert_eq!(with_2.depth(), 4);
+    }
+
+    assert_eq!(s.len(), 0);
+    assert_eq!(s.depth(), 0);
+}
+
+pub fn map_create_empty<M: StackedMap<Key = i32, Value = &'static str>>() {
+    let m = M::empty();
+    assert!(m.is_empty());
+    assert_eq!(m.depth(), 0);
+    assert_eq!(m.get(1), None);
+    assert!(!m.contains_key(2));
+    assert_eq!(m.iter().count(), 0);
+}
+
+pub fn map_shadowing<M: StackedMap<Key = i32, Value = &'static str>>() {
+    let mut m = M::empty();
+
+    {
+        let mut with_1 = m.insert(1, "one");
+        assert_eq!(with_1.get(1), Some(&"one"));
+        assert_eq!(with_1.get(2), None);
+        assert_eq!(with_1.len(), 1);
+
+        {
+            let mut shadowed = with_1.insert(1, "uno");
+            assert_eq!(shadowed.get(1), Some(&"uno"));
+            assert_eq!(shadowed.len(), 1);
+            assert_eq!(shadowed.depth(), 2);
+            assert_eq!(shadowed.iter().collect::<Vec<_>>(), vec![(&1, &"uno")]);
+
+            let with_2 = shadowed.insert(2, "two");
+            assert_eq!(with_2.len(), 2);
+            let mut bindings = with_2.iter().collect::<Vec<_>>();
+            bindings.sort_unstable();
+            assert_eq!(bindings, vec![(&1, &"uno"), (&2, &"two")]);
+        }
+
+        // shadowed value is back
+        assert_eq!(with_1.get(1), Some(&"one"));
+        assert!(!with_1.contains_key(2));
+        assert_eq!(with_1.iter().collect::<Vec<_>>(), vec![(&1, &"one")]);
+    }
+
+    assert!(!m.contains_key(1));
+    assert!(m.is_empty());
+}
+
+pub fn map_fork<M: StackedMap<Key = i32, Value = &'static str>>() {
+    fn recurse(mut map: impl StackedMap<Key = i32, Value = &'static str>, val: i32) -> usize {
+        if val == 0 {
+            map.iter().count()
+        } else if val & 1 == 1 {
+            recurse(map.insert(val % 5, "odd"), val - 1)
+        } else {
+            recurse(map.fork(), val - 1)
+        }
+    }
+
+    // odd numbers modulo 5 cover every remainder
+    assert_eq!(recurse(M::empty(), 100), 5);
+}
+
+pub fn bag_counts<B: StackedBag<Item = i32>>() {
+    let mut b = B::empty();
+    assert!(b.is_empty());
+    assert_eq!(b.count(1), 0);
+
+    {
+        let mut with_1 = b.extend(1);
+        assert_eq!(with_1.count(1), 1);
+        assert!(with_1.contains(1));
+
+        {
+            let mut with_1_twice = with_1.extend(1);
+            assert_eq!(with_1_twice.count(1), 2);
+            assert_eq!(with_1_twice.len(), 2);
+            assert_eq!(with_1_twice.depth(), 2);
+
+            let mut forked = with_1_twice.fork();
+            let with_2 = forked.extend(2);
+            assert_eq!(with_2.count(1), 2);
+            assert_eq!(with_2.count(2), 1);
+            assert_eq!(with_2.len(), 3);
+            assert_eq!(with_2.depth(), 4);
+            let mut counts = with_2.iter().collect::<Vec<_>>();
+            counts.sort_unstable();
+            assert_eq!(counts, vec![(&1, 2), (&2, 1)]);
+        }
+
+        assert_eq!(with_1.count(1), 1);
+        assert!(!with_1.contains(2));
+        assert_eq!(with_1.iter().collect::<Vec<_>>(), vec![(&1, 1)]);
+    }
+
+    assert_eq!(b.count(1), 0);
+    assert!(b.is_empty());
+}
+
+pub fn bag_odd_to_100<B: StackedBag<Item = i32>>() {
+    fn recurse(mut bag: impl StackedBag<Item = i32>, val: i32) -> Vec<(i32, usize)> {
+        if val == 0 {
+            bag.iter().map(|(item, count)| (*item, count)).collect()
+        } else if val & 1 == 1 {
+            recurse(bag.extend(val % 10), val - 1)
+        } else {
+            recurse(bag.fork(), val - 1)
+        }
+    }
+
+    let mut counts = recurse(B::empty(), 100);
+    counts.sort_unstable();
+    assert_eq!(counts, vec![(1, 10), (3, 10), (5, 10), (7, 10), (9, 10)]);
+}
+
+pub fn extend_many<S: StackedSet<Item = i32>>() {
+    fn with_1_and_2<S: StackedSet<Item = i32>>(
+        set: &mut S,
+    ) -> impl StackedSet<Item = i32> + use<'_, S> {
+        set.extend_many([1, 2])
+    }
+
+    let mut s = S::empty();
+
+    {
+        let mut with_3 = s.extend(3);
+        {
+            let mut with_many = with_1_and_2(&mut with_3);
+            assert!(with_many.contains(1));
+            assert!(with_many.contains(2));
+            assert!(with_many.contains(3));
+            assert_eq!(with_many.len(), 3);
+            assert_eq!(with_many.depth(), 2);
+
+            // duplicates are not added twice
+            let items = [3, 4, 4, 1];
+            let with_4 = with_many.extend_many(items);
+            assert_eq!(with_4.len(), 4);
+            let mut all = with_4.iter().copied().collect::<Vec<_>>();
+            all.sort_unstable();
+            assert_eq!(all, vec![1, 2, 3, 4]);
+        }
+        assert!(!with_3.contains(1));
+        assert!(!with_3.contains(2));
+        assert!(with_3.contains(3));
+        assert_eq!(with_3.len(), 1);
+    }
+
+    assert!(s.is_empty());
+}
+
+pub fn try_extend<S: StackedSet<Item = i32>>() {
+    let mut s = S::empty();
+
+    let mut with_1 = match s.try_extend(1) {
+        Extended::Inserted(with_1) => with_1,
+        Extended::AlreadyPresent(_) => panic!("1 was not present in an empty set"),
+    };
+    assert!(with_1.contains(1));
+    assert_eq!(with_1.len(), 1);
+
+    {
+        let again = with_1.try_extend(1);
+        assert!(again.is_already_present());
+        let again = again.into_inner();
+        assert!(again.contains(1));
+        assert_eq!(again.len(), 1);
+        assert_eq!(again.depth(), 2);
+    }
+
+    {
+        let with_2 = with_1.try_extend(2);
+        assert!(with_2.is_inserted());
+        assert_eq!(with_2.into_inner().len(), 2);
+    }
+
+    drop(with_1);
+    assert!(s.is_empty());
+}
+
+pub fn extend_unique<S: StackedSet<Item = i32>>() {
+    fn recurse(set: &mut impl StackedSet<Item = i32>, val: i32) -> Vec<i32> {
+        if val == 0 {
+            set.iter_path().copied().collect()
+        } else {
+            recurse(&mut set.extend_unique(val), val - 1)
+        }
+    }
+
+    let mut s = S::empty();
+    assert_eq!(recurse(&mut s, 5), [5, 4, 3, 2, 1]);
+    assert!(s.is_empty());
+
+    let mut with_1 = s.extend_unique(1);
+    assert!(with_1.contains(1));
+    assert_eq!((with_1.len(), with_1.depth()), (1, 1));
+    if cfg!(debug_assertions) {
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _ = with_1.extend_unique(1);
+        }));
+        assert!(panicked.is_err());
+    }
+    drop(with_1);
+    assert!(s.is_empty());
+}
+
+pub fn frames<S: StackedSet<Item = i32>>() {
+    let mut s = S::empty();
+    assert_eq!(s.frames().count(), 0);
+    assert_eq!(s.top(), None);
+    assert!(!s.is_fork());
+
+    let mut with_1 = s.extend(1);
+    assert_eq!(with_1.top(), Some(&1));
+    assert!(!with_1.is_fork());
+
+    let mut forked = with_1.fork();
+    assert_eq!(forked.top(), None);
+    assert!(forked.is_fork());
+
+    let mut with_1_again = forked.extend(1);
+    assert_eq!(with_1_again.top(), None);
+    assert!(with_1_again.is_fork());
+
+    let items = [2, 1, 3];
+    let mut with_many = with_1_again.extend_many(items);
+    assert_eq!(with_many.top(), Some(&3));
+    assert!(!with_many.is_fork());
+
+    let with_4 = with_many.extend(4);
+    assert_eq!(
+        with_4.frames().collect::<Vec<_>>(),
+        vec![
+            (5, Some(&4)),
+            (4, Some(&3)),
+            (4, Some(&2)),
+            (3, None),
+            (2, None),
+            (1, Some(&1)),
+        ]
+    );
+}
+
+pub fn iter_path<S: StackedSet<Item = i32>>() {
+    fn recurse(mut set: impl StackedSet<Item = i32>, val: i32) -> (Vec<i32>, Vec<i32>) {
+        if val == 100 {
+            (
+                set.iter_path().copied().collect(),
+                set.iter_path_rev().copied().collect(),
+            )
+        } else if val % 3 == 0 {
+            recurse(set.fork(), val + 1)
+        } else {
+            // duplicates are not a part of the path
+            recurse(set.extend(val % 50), val + 1)
+        }
+    }
+
+    let mut expected = Vec::new();
+    for val in (1..100).filter(|val| val % 3 != 0) {
+        if !expected.contains(&(val % 50)) {
+            expected.push(val % 50);
+        }
+    }
+    let (path, path_rev) = recurse(S::empty(), 1);
+    assert_eq!(path, expected);
+    assert_eq!(path_rev, expected.into_iter().rev().collect::<Vec<_>>());
+}
+
+pub fn with_extended<S: StackedSet<Item = i32>>() {
+    // counts simple paths starting at `node` in a small cyclic graph
+    fn simple_paths(set: &mut impl StackedSet<Item = i32>, node: i32) -> usize {
+        set.with_extended_if_absent(node, |inner| {
+            1 + simple_paths(inner, (node + 1) % 5) + simple_paths(inner, (node + 2) % 5)
+        })
+        .unwrap_or(0)
+    }
+
+    fn simple_paths_naive(path: &mut Vec<i32>, node: i32) -> usize {
+        if path.contains(&node) {
+            return 0;
+        }
+        path.push(node);
+        let count =
+            1 + simple_paths_naive(path, (node + 1) % 5) + simple_paths_naive(path, (node + 2) % 5);
+        path.pop();
+        count
+    }
+
+    let mut s = S::empty();
+    assert_eq!(
+        simple_paths(&mut s, 0),
+        simple_paths_naive(&mut Vec::new(), 0)
+    );
+    assert!(s.is_empty());
+
+    let depth = s.with_extended(1, |inner| {
+        assert!(inner.contains(1));
+        inner.with_fork(|inner| {
+            assert!(inner.is_fork());
+            inner.with_extended(1, |inner| inner.depth())
+        })
+    });
+    assert_eq!(depth, 3);
+    assert!(!s.contains(1));
+}
+
+pub fn dyn_set<S: StackedSet<Item = i32>>() {
+    fn recurse(set: &mut dyn DynStackedSet<Item = i32>, val: i32, out: &mut Vec<i32>) {
+        if val == 0 {
+            assert_eq!(set.depth_dyn(), 10);
+            set.for_each_dyn(&mut |item| out.push(*item));
+            #[cfg(feature = "alloc")]
+            assert_eq!(set.iter_boxed().count(), set.len_dyn());
+        } else if val & 1 == 1 {
+            let inserted = set.extend_dyn(val, &mut |inner| {
+                assert!(inner.contains_dyn(&val));
+                recurse(inner, val - 1, out);
+            });
+            assert!(inserted);
+        } else {
+            set.fork_dyn(&mut |inner| recurse(inner, val - 1, out));
+        }
+    }
+
+    let mut s = S::empty();
+    let mut out = Vec::new();
+    recurse(&mut s, 10, &mut out);
+    out.sort_unstable();
+    assert_eq!(out, vec![1, 3, 5, 7, 9]);
+    assert!(s.is_empty());
+
+    let inserted = s.extend_dyn(1, &mut |inner| {
+        assert!(!inner.extend_dyn(1, &mut |inner| assert_eq!(inner.len_dyn(), 1)));
+    });
+    assert!(inserted);
+}
+
+pub fn predicates<S: StackedSet<Item = i32>>() {
+    let mut s = S::empty();
+    assert!(!s.any(|_| true));
+    assert_eq!(s.find(|_| true), None);
+    assert_eq!(s.position(|_| true), None);
+
+    let mut s1 = s.extend(10);
+    let mut s2 = s1.fork();
+    let mut s3 = s2.extend_many([21, 25, 10, 30]);
+    let s4 = s3.extend(5);
+    // path is 10, 21, 25, 30, 5
+    assert!(s4.any(|item| *item > 25));
+    assert!(!s4.any(|item| *item > 30));
+    assert_eq!(s4.find(|item| item % 7 == 0), Some(&21));
+    assert_eq!(s4.find(|item| item % 7 == 1), None);
+    assert_eq!(s4.position(|item| *item == 10), Some(0));
+    assert_eq!(s4.position(|item| *item == 5), Some(4));
+    assert_eq!(s4.position(|item| item % 5 == 0 && *item > 20), Some(3));
+    assert_eq!(s4.position(|item| *item < 0), None);
+
+    assert_eq!(s4.find_in_range(20..30, |item| item % 5 == 0), Some(&25));
+    assert_eq!(s4.find_in_range(20..=30, |item| item % 10 == 0), Some(&30));
+    assert_eq!(s4.find_in_range(..10, |_| true), Some(&5));
+    assert_eq!(s4.find_in_range(11..21, |_| true), None);
+    assert_eq!(s4.find_in_range(0..100, |item| *item > 30), None);
+}
+
+pub fn enter<S: StackedSet<Item = i32>>() {
+    fn visit(set: &mut impl StackedSet<Item = i32>, node: i32) -> Result<(), String> {
+        // graph is 1 -> 2 -> 3 -> 4 -> 2
+        let next = if node == 4 { 2 } else { node + 1 };
+        let mut set = set.enter(node).map_err(|err| err.to_string())?;
+        visit(&mut set, next)
+    }
+
+    let mut s = S::empty();
+    assert_eq!(
+        visit(&mut s, 1).unwrap_err(),
+        "cycle detected: 2 -> 3 -> 4 -> 2"
+    );
+    assert!(s.is_empty());
+
+    let mut s1 = s.enter(1).unwrap();
+    let mut s2 = s1.fork();
+    let mut s3 = s2.extend_many([2, 3]);
+    let Err(err): Result<_, CycleError<i32, _>> = s3.enter(1) else {
+        panic!("1 was entered twice");
+    };
+    assert_eq!(err.item(), &1);
+    assert_eq!(err.path().len(), 3);
+    assert!(err.path().eq(&[1, 2, 3]));
+    assert_eq!(
+        format!("{err:?}"),
+        "CycleError { item: 1, path: [1, 2, 3] }"
+    );
+    assert_eq!(err.into_item(), 1);
+    let s4 = s3.enter(4).unwrap();
+    assert_eq!(s4.top(), Some(&4));
+}
+
+pub fn bounded<S: StackedSet<Item = i32>>() {
+    fn recurse(set: &mut impl StackedSet<Item = i32>, val: i32) -> usize {
+        if val == 0 {
+            set.depth()
+        } else {
+            recurse(&mut set.extend(val), val - 1)
+        }
+    }
+
+    let mut s = Bounded::<S, ConstLimits<3, 2>>::empty();
+    let mut s1 = s.checked_extend(1).unwrap();
+    assert!(s1.checked_try_extend(1).unwrap().is_already_present());
+    let mut s2 = s1.checked_extend(2).unwrap();
+    assert_eq!(
+        s2.checked_extend(3).err(),
+        Some(LimitExceeded::Len { limit: 2 })
+    );
+    assert_eq!(
+        s2.checked_extend_many([2, 3]).err(),
+        Some(LimitExceeded::Len { limit: 2 })
+    );
+    assert!(s2.contains_all([1, 2]) && !s2.contains(3));
+    let mut s3 = s2.checked_extend_many([1, 2, 1]).unwrap();
+    assert_eq!(s3.depth(), 3);
+    assert_eq!(
+        s3.checked_fork().err(),
+        Some(LimitExceeded::Depth { limit: 3 })
+    );
+    assert_eq!(
+        s3.checked_extend(1).err(),
+        Some(LimitExceeded::Depth { limit: 3 })
+    );
+    assert_eq!(
+        LimitExceeded::Depth { limit: 3 }.to_string(),
+        "stacked set depth limit of 3 exceeded"
+    );
+
+    // generic code keeps working, until a limit is reached
+    let limits = RuntimeLimits {
+        max_depth: 10,
+        max_len: usize::MAX,
+    };
+    let mut s = Bounded::new(S::empty(), limits);
+    assert_eq!(recurse(&mut s, 10), 10);
+    let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| recurse(&mut s, 11)));
+    assert!(panicked.is_err());
+    assert!(s.inner().is_empty());
+}
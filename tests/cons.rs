@@ -4,3 +4,5 @@
 mod common;
 
 tests!(stacked_set::StackCons::<'static, i32>);
+map_tests!(stacked_set::StackConsMap::<'static, i32, &'static str>);
+bag_tests!(stacked_set::StackConsBag::<'static, i32>);
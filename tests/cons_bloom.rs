@@ -0,0 +1,6 @@
+#![allow(missing_docs)]
+#![cfg(feature = "cons")]
+
+mod common;
+
+tests!(stacked_set::StackBloomCons::<'static, i32>);
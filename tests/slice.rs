@@ -0,0 +1,43 @@
+#![allow(missing_docs)]
+#![cfg(feature = "slice")]
+
+use stacked_set::{
+    SliceSet, StackedSet,
+    collection::{CapacityExceeded, SliceCollection},
+};
+
+#[test]
+fn stack_buffer() {
+    let mut buffer = [None; 4];
+    {
+        let mut set = SliceSet::new(SliceCollection::with_buffer(&mut buffer));
+        let mut with_2 = set.checked_extend_many([1, 2, 1]).unwrap();
+        let mut with_3 = with_2.checked_extend(3).unwrap();
+        assert_eq!(
+            with_3.checked_extend_many([4, 5]).err(),
+            Some(CapacityExceeded { capacity: 4 })
+        );
+        let mut full = with_3.checked_extend(4).unwrap();
+        assert_eq!(
+            full.checked_extend(5).err(),
+            Some(CapacityExceeded { capacity: 4 })
+        );
+        // present item takes no room
+        assert!(full.checked_try_extend(1).is_ok());
+        assert!(full.iter().eq(&[1, 2, 3, 4]));
+    }
+    // frames took their items with them
+    assert!(buffer.iter().all(Option::is_none));
+
+    {
+        let mut set = SliceSet::new(SliceCollection::with_buffer(&mut buffer));
+        set.checked_extend_many([5, 6]).unwrap().commit();
+        assert_eq!(set.len(), 2);
+    }
+    // committed items stay in the buffer, until it's reused
+    assert_eq!(buffer, [Some(5), Some(6), None, None]);
+    let mut set = SliceSet::new(SliceCollection::with_buffer(&mut buffer));
+    assert!(set.is_empty());
+    let with_all = set.checked_extend_many([7, 8, 9, 10]).unwrap();
+    assert!(with_all.iter().eq(&[7, 8, 9, 10]));
+}
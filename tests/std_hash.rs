@@ -4,3 +4,5 @@
 mod common;
 
 tests!(stacked_set::StdHash::<'static, i32>);
+map_tests!(stacked_set::StdHashMap::<'static, i32, &'static str>);
+bag_tests!(stacked_set::StdHashBag::<'static, i32>);